@@ -1,37 +1,111 @@
+use crate::lexer::Span;
 use std::fmt::Display;
+use std::rc::Rc;
 
-#[derive(Debug)]
-pub enum Statement {
+#[derive(Debug, Clone)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+impl Statement {
+    pub fn new(kind: StatementKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum StatementKind {
     Let(Identifier, Expression),
-    If(Expression, Box<Statement>, Box<Statement>),
     Return(Expression),
     Expression(Expression),
+    Error,
 }
 
-impl Display for Statement {
+impl Display for StatementKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "{}",
             match self {
                 Self::Let(i, e) => format!("let {} = {}", i, e),
-                Self::If(e, s1, s2) => format!("if {} {{\n{}\n}} else {{\n{}\n}}", e, s1, s2),
                 Self::Return(e) => format!("return {}", e),
                 Self::Expression(e) => format!("e{{{}}}", e),
+                Self::Error => "ERROR".to_string(),
             }
         )
     }
 }
 
-#[derive(Debug)]
-pub enum Expression {
+#[derive(Debug, Clone)]
+pub struct BlockStatement {
+    pub statements: Vec<Statement>,
+    pub span: Span,
+}
+
+impl Display for BlockStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", join(&self.statements, "\n"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub span: Span,
+}
+
+impl Expression {
+    pub fn new(kind: ExpressionKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ExpressionKind {
     Variable(String),
     IntLiteral(i32),
+    BoolLiteral(bool),
+    StringLiteral(String),
+    ArrayLiteral(Vec<Expression>),
+    HashLiteral(Vec<(Expression, Expression)>),
+    Prefix(PrefixOperator, Box<Expression>),
     Binary(BinaryOperator, Box<Expression>, Box<Expression>),
     Grouped(Box<Expression>),
+    If {
+        condition: Box<Expression>,
+        consequence: BlockStatement,
+        alternative: Option<BlockStatement>,
+    },
+    Function {
+        params: Vec<Identifier>,
+        // Shared with the closures created from this literal.
+        body: Rc<BlockStatement>,
+    },
+    Call {
+        callee: Box<Expression>,
+        args: Vec<Expression>,
+    },
+    Index {
+        left: Box<Expression>,
+        index: Box<Expression>,
+    },
+    Error,
 }
 
-impl Display for Expression {
+impl Display for ExpressionKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -39,17 +113,67 @@ impl Display for Expression {
             match self {
                 Self::Variable(s) => format!("VAR({s})"),
                 Self::IntLiteral(n) => format!("INT({n})"),
-                Self::Binary(o, e1, e2) => format!("{e1} {o} {e2}"),
+                Self::BoolLiteral(b) => format!("BOOL({b})"),
+                Self::StringLiteral(s) => format!("STR({s:?})"),
+                Self::ArrayLiteral(elements) => format!("[{}]", join(elements, ", ")),
+                Self::HashLiteral(pairs) => {
+                    let pairs: Vec<String> =
+                        pairs.iter().map(|(k, v)| format!("{k}: {v}")).collect();
+                    format!("{{{}}}", pairs.join(", "))
+                }
+                Self::Prefix(o, e) => format!("({o}{e})"),
+                Self::Binary(o, e1, e2) => format!("({e1} {o} {e2})"),
                 Self::Grouped(e) => format!("({e})"),
+                Self::If {
+                    condition,
+                    consequence,
+                    alternative: Some(alternative),
+                } => format!("if {condition} {{\n{consequence}\n}} else {{\n{alternative}\n}}"),
+                Self::If {
+                    condition,
+                    consequence,
+                    alternative: None,
+                } => format!("if {condition} {{\n{consequence}\n}}"),
+                Self::Function { params, body } => {
+                    format!("fn({}) {{\n{}\n}}", join(params, ", "), body)
+                }
+                Self::Call { callee, args } => format!("{callee}({})", join(args, ", ")),
+                Self::Index { left, index } => format!("({left}[{index}])"),
+                Self::Error => "ERROR".to_string(),
             }
         )
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum PrefixOperator {
+    Not,
+    Negate,
+}
+
+impl Display for PrefixOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Not => "!",
+                Self::Negate => "-",
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum BinaryOperator {
     Add,
     Subtract,
+    Multiply,
+    Divide,
+    Lt,
+    Gt,
+    Eq,
+    NotEq,
 }
 
 impl Display for BinaryOperator {
@@ -60,14 +184,21 @@ impl Display for BinaryOperator {
             match self {
                 Self::Add => "+",
                 Self::Subtract => "-",
+                Self::Multiply => "*",
+                Self::Divide => "/",
+                Self::Lt => "<",
+                Self::Gt => ">",
+                Self::Eq => "==",
+                Self::NotEq => "!=",
             }
         )
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Identifier {
     pub name: String,
+    pub span: Span,
 }
 
 impl Display for Identifier {
@@ -87,10 +218,18 @@ impl Display for Program {
             .statements
             .iter()
             .map(|stmt| -> String { stmt.to_string() });
-        write!(f, "Program [\n")?;
+        writeln!(f, "Program [")?;
         for line in strings {
-            write!(f, "{}\n", line)?;
+            writeln!(f, "{}", line)?;
         }
         write!(f, "]")
     }
 }
+
+fn join<T: Display>(items: &[T], separator: &str) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
@@ -0,0 +1,114 @@
+use crate::evaluator::{RuntimeError, RuntimeErrorKind};
+use crate::lexer::Span;
+use crate::object::{Builtin, BuiltinFn, Object};
+
+const BUILTINS: &[(&str, BuiltinFn)] = &[
+    ("len", len),
+    ("puts", puts),
+    ("first", first),
+    ("last", last),
+    ("rest", rest),
+    ("push", push),
+];
+
+// Consulted after the environment, so user bindings shadow builtins.
+pub fn lookup(name: &str) -> Option<Object> {
+    BUILTINS
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|&(name, func)| Object::Builtin(Builtin { name, func }))
+}
+
+pub fn names() -> impl Iterator<Item = &'static str> {
+    BUILTINS.iter().map(|&(name, _)| name)
+}
+
+// Builtins do not know where they were called from; the evaluator replaces
+// the empty span with the span of the call.
+fn error(kind: RuntimeErrorKind) -> RuntimeError {
+    RuntimeError::new(kind, Span::default())
+}
+
+fn check_arity(
+    function: &'static str,
+    args: &[Object],
+    expected: usize,
+) -> Result<(), RuntimeError> {
+    if args.len() == expected {
+        Ok(())
+    } else {
+        Err(error(RuntimeErrorKind::WrongArgumentCount {
+            function: Some(function),
+            expected,
+            got: args.len(),
+        }))
+    }
+}
+
+fn invalid_argument(function: &'static str, expected: &'static str, got: &Object) -> RuntimeError {
+    error(RuntimeErrorKind::InvalidArgument {
+        function,
+        expected,
+        got: got.type_name(),
+    })
+}
+
+fn array_argument<'a>(
+    function: &'static str,
+    arg: &'a Object,
+) -> Result<&'a [Object], RuntimeError> {
+    match arg {
+        Object::Array(elements) => Ok(elements),
+        other => Err(invalid_argument(function, "ARRAY", other)),
+    }
+}
+
+// Strings are measured in characters, not bytes.
+fn len(args: &[Object]) -> Result<Object, RuntimeError> {
+    check_arity("len", args, 1)?;
+    let len = match &args[0] {
+        Object::String(s) => s.chars().count(),
+        Object::Array(elements) => elements.len(),
+        Object::Hash(hash) => hash.len(),
+        other => return Err(invalid_argument("len", "STRING, ARRAY or HASH", other)),
+    };
+    Ok(Object::Integer(i32::try_from(len).unwrap_or(i32::MAX)))
+}
+
+fn puts(args: &[Object]) -> Result<Object, RuntimeError> {
+    for arg in args.iter() {
+        println!("{arg}");
+    }
+    Ok(Object::Null)
+}
+
+// `first`, `last` and `rest` give `null` for an empty array.
+fn first(args: &[Object]) -> Result<Object, RuntimeError> {
+    check_arity("first", args, 1)?;
+    let elements = array_argument("first", &args[0])?;
+    Ok(elements.first().cloned().unwrap_or(Object::Null))
+}
+
+fn last(args: &[Object]) -> Result<Object, RuntimeError> {
+    check_arity("last", args, 1)?;
+    let elements = array_argument("last", &args[0])?;
+    Ok(elements.last().cloned().unwrap_or(Object::Null))
+}
+
+fn rest(args: &[Object]) -> Result<Object, RuntimeError> {
+    check_arity("rest", args, 1)?;
+    let elements = array_argument("rest", &args[0])?;
+    Ok(match elements.split_first() {
+        Some((_, rest)) => Object::Array(rest.to_vec()),
+        None => Object::Null,
+    })
+}
+
+// Arrays are values: `push` returns a new array and leaves its argument
+// unchanged.
+fn push(args: &[Object]) -> Result<Object, RuntimeError> {
+    check_arity("push", args, 2)?;
+    let mut elements = array_argument("push", &args[0])?.to_vec();
+    elements.push(args[1].clone());
+    Ok(Object::Array(elements))
+}
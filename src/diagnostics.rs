@@ -0,0 +1,195 @@
+use crate::evaluator::RuntimeError;
+use crate::lexer::{IllegalKind, Span, Token};
+use crate::parser::{ParseError, ParseErrorKind};
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Error => "error",
+                Self::Warning => "warning",
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+    pub label: Option<String>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: impl Into<String>, span: Span) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            span,
+            label: None,
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>, span: Span) -> Self {
+        Self::new(Severity::Error, message, span)
+    }
+
+    pub fn warning(message: impl Into<String>, span: Span) -> Self {
+        Self::new(Severity::Warning, message, span)
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    // Renders in the familiar compiler layout:
+    //
+    //   error: expected IDENT, got INT(5)
+    //    --> 1:5
+    //     |
+    //   1 | let 5 = 1;
+    //     |     ^ expected IDENT
+    //
+    // Spans covering several lines are underlined up to the end of their
+    // first line.
+    pub fn render(&self, source: &str, color: bool) -> String {
+        let style = Style { color };
+        let input = source.as_bytes();
+        let start = self.span.start_position(input);
+        let end = self.span.end_position(input);
+
+        let line = source.split('\n').nth(start.row as usize).unwrap_or("");
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let line_number = (start.row + 1).to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        let col = (start.col as usize).min(line.len());
+        let underline_end = if end.row == start.row {
+            (end.col as usize).clamp(col, line.len())
+        } else {
+            line.len()
+        };
+        // Columns count characters, like the underline indent, rather than
+        // the bytes that spans are measured in.
+        let before = line.get(..col).unwrap_or("");
+        let column = before.chars().count() + 1;
+        // Keep tabs so the underline lines up with the source text.
+        let indent: String = before
+            .chars()
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = line
+            .get(col..underline_end)
+            .map_or(0, |text| text.chars().count())
+            .max(1);
+        let severity_color = match self.severity {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+        };
+        let underline = style.paint(severity_color, &"^".repeat(width));
+        let label = match &self.label {
+            Some(label) => format!(" {}", style.paint(severity_color, label)),
+            None => String::new(),
+        };
+
+        let mut lines = vec![
+            format!(
+                "{}{}",
+                style.paint(severity_color, &self.severity.to_string()),
+                style.paint(BOLD, &format!(": {}", self.message))
+            ),
+            format!(
+                "{}{} {}:{}",
+                gutter,
+                style.paint(BLUE, "-->"),
+                start.row + 1,
+                column
+            ),
+            format!("{} {}", gutter, style.paint(BLUE, "|")),
+            format!(
+                "{} {} {}",
+                style.paint(BLUE, &line_number),
+                style.paint(BLUE, "|"),
+                line
+            ),
+            format!(
+                "{} {} {}{}{}",
+                gutter,
+                style.paint(BLUE, "|"),
+                indent,
+                underline,
+                label
+            ),
+        ];
+        for note in self.notes.iter() {
+            lines.push(format!(
+                "{} {} note: {}",
+                gutter,
+                style.paint(BLUE, "="),
+                note
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(err: &ParseError) -> Self {
+        let label = match err.kind {
+            ParseErrorKind::InvalidLiteral => match err.found.token {
+                Token::ILLEGAL(IllegalKind::InvalidEscape, _) => {
+                    "contains an invalid escape sequence".to_string()
+                }
+                _ => "does not fit in a 32-bit integer".to_string(),
+            },
+            ParseErrorKind::UnterminatedComment => "missing closing `*/`".to_string(),
+            ParseErrorKind::UnterminatedString => "missing closing `\"`".to_string(),
+            _ => format!("expected {}", err.expected_list()),
+        };
+        Diagnostic::error(err.message(), err.span).with_label(label)
+    }
+}
+
+impl From<&RuntimeError> for Diagnostic {
+    fn from(err: &RuntimeError) -> Self {
+        Diagnostic::error(err.to_string(), err.span)
+    }
+}
+
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const RESET: &str = "\x1b[0m";
+
+struct Style {
+    color: bool,
+}
+
+impl Style {
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("{code}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    }
+}
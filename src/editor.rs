@@ -0,0 +1,86 @@
+use crate::repl::{self, Repl};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+const HISTORY_FILE: &str = ".monkey_history";
+
+// Runs the REPL with line editing, tab completion and history kept in the
+// home directory. Ctrl-C drops the statement being entered, Ctrl-D exits.
+pub fn run(repl: &mut Repl) -> rustyline::Result<()> {
+    let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(ReplHelper::default()));
+
+    let history = history_path();
+    if let Some(path) = &history {
+        // A missing history file just means this is the first session.
+        let _ = editor.load_history(path);
+    }
+
+    let mut output = io::stdout();
+    loop {
+        if let Some(helper) = editor.helper_mut() {
+            helper.names = repl.completion_names();
+        }
+        match editor.readline(repl.prompt()) {
+            Ok(line) => {
+                if !line.trim().is_empty() {
+                    editor.add_history_entry(line.as_str())?;
+                }
+                repl.feed_line(&line, &mut output)?;
+            }
+            Err(ReadlineError::Interrupted) => repl.cancel(),
+            Err(ReadlineError::Eof) => {
+                repl.finish(&mut output)?;
+                break;
+            }
+            Err(err) => return Err(err),
+        }
+        output.flush()?;
+    }
+
+    if let Some(path) = &history {
+        if let Err(err) = editor.save_history(path) {
+            eprintln!("warning: cannot save history to {}: {err}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE))
+}
+
+#[derive(Default)]
+struct ReplHelper {
+    names: Vec<String>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(repl::complete(line, pos, &self.names))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
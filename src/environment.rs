@@ -0,0 +1,50 @@
+use crate::object::Object;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub type Env = Rc<RefCell<Environment>>;
+
+// A single lexical scope. Lookups fall back to the enclosing scope, while
+// `set` always binds in the current one, so an inner `let` shadows an outer
+// binding without modifying it.
+#[derive(Debug, Default)]
+pub struct Environment {
+    store: HashMap<String, Object>,
+    outer: Option<Env>,
+}
+
+impl Environment {
+    pub fn new() -> Env {
+        Rc::new(RefCell::new(Self::default()))
+    }
+
+    pub fn new_enclosed(outer: &Env) -> Env {
+        Rc::new(RefCell::new(Self {
+            store: HashMap::new(),
+            outer: Some(Rc::clone(outer)),
+        }))
+    }
+
+    pub fn get(&self, name: &str) -> Option<Object> {
+        match self.store.get(name) {
+            Some(value) => Some(value.clone()),
+            None => self.outer.as_ref()?.borrow().get(name),
+        }
+    }
+
+    pub fn set(&mut self, name: &str, value: Object) {
+        self.store.insert(name.to_string(), value);
+    }
+
+    // Bindings of this scope only, sorted by name.
+    pub fn bindings(&self) -> Vec<(&str, &Object)> {
+        let mut bindings: Vec<(&str, &Object)> = self
+            .store
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+            .collect();
+        bindings.sort_by_key(|&(name, _)| name);
+        bindings
+    }
+}
@@ -0,0 +1,396 @@
+use crate::ast;
+use crate::builtins;
+use crate::environment::Env;
+use crate::environment::Environment;
+use crate::lexer::Span;
+use crate::object::{Function, HashKey, Object};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+// Evaluation recurses on the native stack once per nested expression,
+// including across function calls, so deep recursion is reported as an error
+// instead of overflowing it. A debug build takes up to about 9 KiB of stack per
+// level, so the limit leaves headroom on a default 8 MiB main thread.
+pub const MAX_EVAL_DEPTH: usize = 500;
+
+thread_local! {
+    static EVAL_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
+    pub span: Span,
+}
+
+impl RuntimeError {
+    pub fn new(kind: RuntimeErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum RuntimeErrorKind {
+    UnknownIdentifier(String),
+    IntegerOverflow(ast::BinaryOperator, i32, i32),
+    TypeMismatch(ast::BinaryOperator, &'static str, &'static str),
+    InvalidOperand(ast::PrefixOperator, &'static str),
+    DivisionByZero,
+    NotAFunction(&'static str),
+    // Only builtins have a name to report.
+    WrongArgumentCount {
+        function: Option<&'static str>,
+        expected: usize,
+        got: usize,
+    },
+    InvalidArgument {
+        function: &'static str,
+        expected: &'static str,
+        got: &'static str,
+    },
+    NotIndexable(&'static str),
+    InvalidIndex(&'static str, &'static str),
+    IndexOutOfBounds {
+        index: i32,
+        len: usize,
+    },
+    UnhashableKey(&'static str),
+    StackOverflow,
+    SyntaxError,
+}
+
+impl Display for RuntimeErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownIdentifier(name) => write!(f, "identifier not found: {name}"),
+            Self::IntegerOverflow(o, a, b) => write!(f, "integer overflow: {a} {o} {b}"),
+            Self::TypeMismatch(o, l, r) => write!(f, "type mismatch: {l} {o} {r}"),
+            Self::InvalidOperand(o, t) => write!(f, "invalid operand: {o}{t}"),
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::NotAFunction(t) => write!(f, "not a function: {t}"),
+            Self::InvalidArgument {
+                function,
+                expected,
+                got,
+            } => write!(f, "argument to `{function}` must be {expected}, got {got}"),
+            Self::NotIndexable(t) => write!(f, "index operator not supported: {t}"),
+            Self::InvalidIndex(t, i) => write!(f, "cannot index {t} with {i}"),
+            Self::IndexOutOfBounds { index, len } => {
+                write!(f, "index out of bounds: index {index}, length {len}")
+            }
+            Self::UnhashableKey(t) => write!(f, "unusable as hash key: {t}"),
+            Self::StackOverflow => write!(
+                f,
+                "stack overflow: expressions nested more than {MAX_EVAL_DEPTH} deep"
+            ),
+            Self::SyntaxError => write!(f, "cannot evaluate code that failed to parse"),
+            Self::WrongArgumentCount {
+                function,
+                expected,
+                got,
+            } => {
+                write!(f, "wrong number of arguments")?;
+                if let Some(function) = function {
+                    write!(f, " to `{function}`")?;
+                }
+                write!(f, ": expected {expected}, got {got}")
+            }
+        }
+    }
+}
+
+pub fn eval(program: &ast::Program, env: &Env) -> Result<Object, RuntimeError> {
+    // A `return` outside of any function ends the program with its value.
+    catch_return(eval_statements(&program.statements, env))
+}
+
+// Everything that stops evaluation early travels through the error side of
+// `Result`, so `?` unwinds a `return` from any depth inside an expression up
+// to the enclosing call, and an error all the way up to `eval`.
+enum Flow {
+    Return(Object),
+    Error(RuntimeError),
+}
+
+impl From<RuntimeError> for Flow {
+    fn from(err: RuntimeError) -> Self {
+        Self::Error(err)
+    }
+}
+
+fn eval_statements(stmts: &[ast::Statement], env: &Env) -> Result<Object, Flow> {
+    let mut result = Object::Null;
+    for stmt in stmts.iter() {
+        result = eval_statement(stmt, env)?;
+    }
+    Ok(result)
+}
+
+// Blocks get their own scope: bindings made inside are dropped when the block
+// ends, while outer bindings stay visible and can be shadowed.
+fn eval_block(block: &ast::BlockStatement, env: &Env) -> Result<Object, Flow> {
+    let env = Environment::new_enclosed(env);
+    eval_statements(&block.statements, &env)
+}
+
+fn eval_statement(stmt: &ast::Statement, env: &Env) -> Result<Object, Flow> {
+    match &stmt.kind {
+        ast::StatementKind::Let(ident, expr) => {
+            let value = eval_expression(expr, env)?;
+            env.borrow_mut().set(&ident.name, value);
+            Ok(Object::Null)
+        }
+        ast::StatementKind::Return(expr) => Err(Flow::Return(eval_expression(expr, env)?)),
+        ast::StatementKind::Expression(expr) => eval_expression(expr, env),
+        ast::StatementKind::Error => {
+            Err(RuntimeError::new(RuntimeErrorKind::SyntaxError, stmt.span).into())
+        }
+    }
+}
+
+fn eval_expression(expr: &ast::Expression, env: &Env) -> Result<Object, Flow> {
+    // Errors from the operator helpers below are reported at the whole
+    // expression.
+    let at_expr = |kind| Flow::Error(RuntimeError::new(kind, expr.span));
+    let _depth = EvalDepth::enter().ok_or_else(|| at_expr(RuntimeErrorKind::StackOverflow))?;
+    match &expr.kind {
+        ast::ExpressionKind::Variable(name) => env
+            .borrow()
+            .get(name)
+            .or_else(|| builtins::lookup(name))
+            .ok_or_else(|| at_expr(RuntimeErrorKind::UnknownIdentifier(name.clone()))),
+        ast::ExpressionKind::IntLiteral(n) => Ok(Object::Integer(*n)),
+        ast::ExpressionKind::BoolLiteral(b) => Ok(Object::Boolean(*b)),
+        ast::ExpressionKind::StringLiteral(s) => Ok(Object::String(s.clone())),
+        ast::ExpressionKind::ArrayLiteral(elements) => Ok(Object::Array(
+            elements
+                .iter()
+                .map(|element| eval_expression(element, env))
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        ast::ExpressionKind::HashLiteral(pairs) => {
+            // Later duplicates of a key overwrite earlier ones.
+            let mut hash = BTreeMap::new();
+            for (key_expr, value_expr) in pairs.iter() {
+                let key = eval_expression(key_expr, env)?;
+                let key = hash_key(&key).map_err(|kind| RuntimeError::new(kind, key_expr.span))?;
+                hash.insert(key, eval_expression(value_expr, env)?);
+            }
+            Ok(Object::Hash(hash))
+        }
+        ast::ExpressionKind::Prefix(o, e) => {
+            let value = eval_expression(e, env)?;
+            eval_prefix(o, value).map_err(at_expr)
+        }
+        ast::ExpressionKind::Binary(o, e1, e2) => {
+            let left = eval_expression(e1, env)?;
+            let right = eval_expression(e2, env)?;
+            eval_binary(o, left, right).map_err(at_expr)
+        }
+        ast::ExpressionKind::Grouped(e) => eval_expression(e, env),
+        ast::ExpressionKind::If {
+            condition,
+            consequence,
+            alternative,
+        } => {
+            if is_truthy(&eval_expression(condition, env)?) {
+                eval_block(consequence, env)
+            } else if let Some(alternative) = alternative {
+                eval_block(alternative, env)
+            } else {
+                Ok(Object::Null)
+            }
+        }
+        ast::ExpressionKind::Function { params, body } => Ok(Object::Function(Rc::new(Function {
+            params: params.clone(),
+            body: Rc::clone(body),
+            env: Rc::clone(env),
+        }))),
+        ast::ExpressionKind::Call { callee, args } => {
+            let function = eval_expression(callee, env)?;
+            let args = args
+                .iter()
+                .map(|arg| eval_expression(arg, env))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(apply_function(function, args, expr.span)?)
+        }
+        ast::ExpressionKind::Index { left, index } => {
+            let left = eval_expression(left, env)?;
+            let index = eval_expression(index, env)?;
+            eval_index(left, index).map_err(at_expr)
+        }
+        ast::ExpressionKind::Error => Err(at_expr(RuntimeErrorKind::SyntaxError)),
+    }
+}
+
+fn apply_function(function: Object, args: Vec<Object>, span: Span) -> Result<Object, RuntimeError> {
+    let function = match function {
+        Object::Function(function) => function,
+        Object::Builtin(builtin) => {
+            return (builtin.func)(&args).map_err(|err| RuntimeError::new(err.kind, span))
+        }
+        other => {
+            return Err(RuntimeError::new(
+                RuntimeErrorKind::NotAFunction(other.type_name()),
+                span,
+            ))
+        }
+    };
+    if function.params.len() != args.len() {
+        return Err(RuntimeError::new(
+            RuntimeErrorKind::WrongArgumentCount {
+                function: None,
+                expected: function.params.len(),
+                got: args.len(),
+            },
+            span,
+        ));
+    }
+    let env = Environment::new_enclosed(&function.env);
+    for (param, arg) in function.params.iter().zip(args) {
+        env.borrow_mut().set(&param.name, arg);
+    }
+    catch_return(eval_statements(&function.body.statements, &env))
+}
+
+// Counts an expression for as long as its evaluation is alive, so the depth
+// is restored however it ends.
+struct EvalDepth;
+
+impl EvalDepth {
+    fn enter() -> Option<Self> {
+        EVAL_DEPTH.with(|depth| {
+            if depth.get() >= MAX_EVAL_DEPTH {
+                return None;
+            }
+            depth.set(depth.get() + 1);
+            Some(EvalDepth)
+        })
+    }
+}
+
+impl Drop for EvalDepth {
+    fn drop(&mut self) {
+        EVAL_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+fn catch_return(result: Result<Object, Flow>) -> Result<Object, RuntimeError> {
+    match result {
+        Ok(value) | Err(Flow::Return(value)) => Ok(value),
+        Err(Flow::Error(err)) => Err(err),
+    }
+}
+
+// `!` follows the truthiness rules used by conditionals: only `false` and
+// `null` are falsy, so `!0` is `false` and `!null` is `true`.
+fn eval_prefix(operator: &ast::PrefixOperator, value: Object) -> Result<Object, RuntimeErrorKind> {
+    match (operator, value) {
+        (ast::PrefixOperator::Not, value) => Ok(Object::Boolean(!is_truthy(&value))),
+        (ast::PrefixOperator::Negate, Object::Integer(n)) => n
+            .checked_neg()
+            .map(Object::Integer)
+            .ok_or(RuntimeErrorKind::IntegerOverflow(
+                ast::BinaryOperator::Subtract,
+                0,
+                n,
+            )),
+        (ast::PrefixOperator::Negate, value) => Err(RuntimeErrorKind::InvalidOperand(
+            operator.clone(),
+            value.type_name(),
+        )),
+    }
+}
+
+// Negative array indexes count from the end, so `a[-1]` is the last element.
+// Anything outside `-len..len` is an error rather than `null`. Looking up a
+// missing hash key gives `null`.
+fn eval_index(left: Object, index: Object) -> Result<Object, RuntimeErrorKind> {
+    match (left, index) {
+        (Object::Array(elements), Object::Integer(i)) => {
+            let len = elements.len();
+            let position = if i < 0 {
+                len.checked_sub(i.unsigned_abs() as usize)
+            } else {
+                Some(i as usize)
+            };
+            position
+                .and_then(|position| elements.into_iter().nth(position))
+                .ok_or(RuntimeErrorKind::IndexOutOfBounds { index: i, len })
+        }
+        (Object::Array(_), index) => {
+            Err(RuntimeErrorKind::InvalidIndex("ARRAY", index.type_name()))
+        }
+        (Object::Hash(mut hash), key) => Ok(hash.remove(&hash_key(&key)?).unwrap_or(Object::Null)),
+        (left, _) => Err(RuntimeErrorKind::NotIndexable(left.type_name())),
+    }
+}
+
+fn hash_key(object: &Object) -> Result<HashKey, RuntimeErrorKind> {
+    HashKey::from_object(object).ok_or(RuntimeErrorKind::UnhashableKey(object.type_name()))
+}
+
+fn eval_binary(
+    operator: &ast::BinaryOperator,
+    left: Object,
+    right: Object,
+) -> Result<Object, RuntimeErrorKind> {
+    match (left, right) {
+        (Object::Integer(a), Object::Integer(b)) => eval_integer_binary(operator, a, b),
+        (Object::String(a), Object::String(b)) if *operator == ast::BinaryOperator::Add => {
+            Ok(Object::String(a + &b))
+        }
+        (Object::String(a), Object::String(b)) if *operator == ast::BinaryOperator::Eq => {
+            Ok(Object::Boolean(a == b))
+        }
+        (Object::String(a), Object::String(b)) if *operator == ast::BinaryOperator::NotEq => {
+            Ok(Object::Boolean(a != b))
+        }
+        (Object::Boolean(a), Object::Boolean(b)) if *operator == ast::BinaryOperator::Eq => {
+            Ok(Object::Boolean(a == b))
+        }
+        (Object::Boolean(a), Object::Boolean(b)) if *operator == ast::BinaryOperator::NotEq => {
+            Ok(Object::Boolean(a != b))
+        }
+        (left, right) => Err(RuntimeErrorKind::TypeMismatch(
+            operator.clone(),
+            left.type_name(),
+            right.type_name(),
+        )),
+    }
+}
+
+fn eval_integer_binary(
+    operator: &ast::BinaryOperator,
+    a: i32,
+    b: i32,
+) -> Result<Object, RuntimeErrorKind> {
+    let result = match operator {
+        ast::BinaryOperator::Add => a.checked_add(b),
+        ast::BinaryOperator::Subtract => a.checked_sub(b),
+        ast::BinaryOperator::Multiply => a.checked_mul(b),
+        ast::BinaryOperator::Divide if b == 0 => return Err(RuntimeErrorKind::DivisionByZero),
+        ast::BinaryOperator::Divide => a.checked_div(b),
+        ast::BinaryOperator::Lt => return Ok(Object::Boolean(a < b)),
+        ast::BinaryOperator::Gt => return Ok(Object::Boolean(a > b)),
+        ast::BinaryOperator::Eq => return Ok(Object::Boolean(a == b)),
+        ast::BinaryOperator::NotEq => return Ok(Object::Boolean(a != b)),
+    };
+    result
+        .map(Object::Integer)
+        .ok_or_else(|| RuntimeErrorKind::IntegerOverflow(operator.clone(), a, b))
+}
+
+fn is_truthy(object: &Object) -> bool {
+    !matches!(object, Object::Null | Object::Boolean(false))
+}
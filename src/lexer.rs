@@ -1,13 +1,25 @@
 use std::fmt::Display;
 
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum IllegalKind {
+    Character,
+    IntegerOverflow,
+    InvalidEscape,
+    UnterminatedComment,
+    UnterminatedString,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
     // Special
-    ILLEGAL,
+    ILLEGAL(IllegalKind, String),
     EOF,
     // Identifiers
     IDENT(String),
     INT(i32),
+    STRING(String),
+    // Trivia, only produced when the lexer preserves comments
+    COMMENT(String),
     // Operators
     ASSIGN,
     PLUS,
@@ -22,11 +34,14 @@ pub enum Token {
     NEQ,
     // Delimiters
     COMMA,
+    COLON,
     SEMICOLON,
     LPAREN,
     RPAREN,
     LBRACE,
     RBRACE,
+    LBRACKET,
+    RBRACKET,
     // Keywords
     FUNCTION,
     LET,
@@ -40,10 +55,12 @@ pub enum Token {
 impl Display for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let string = match self {
-            Token::ILLEGAL => "ILLEGAL".to_string(),
+            Token::ILLEGAL(_, string) => format!("ILLEGAL({})", string),
             Token::EOF => "EOF".to_string(),
             Token::IDENT(string) => format!("IDENT({})", string),
             Token::INT(number) => format!("INT({})", number),
+            Token::STRING(string) => format!("STRING({:?})", string),
+            Token::COMMENT(string) => format!("COMMENT({})", string),
             Token::ASSIGN => "ASSIGN".to_string(),
             Token::PLUS => "PLUS".to_string(),
             Token::MINUS => "MINUS".to_string(),
@@ -55,11 +72,14 @@ impl Display for Token {
             Token::EQ => "EQ".to_string(),
             Token::NEQ => "NEQ".to_string(),
             Token::COMMA => "COMMA".to_string(),
+            Token::COLON => "COLON".to_string(),
             Token::SEMICOLON => "SEMICOLON".to_string(),
             Token::LPAREN => "LPAREN".to_string(),
             Token::RPAREN => "RPAREN".to_string(),
             Token::LBRACE => "LBRACE".to_string(),
             Token::RBRACE => "RBRACE".to_string(),
+            Token::LBRACKET => "LBRACKET".to_string(),
+            Token::RBRACKET => "RBRACKET".to_string(),
             Token::FUNCTION => "FUNCTION".to_string(),
             Token::LET => "LET".to_string(),
             Token::TRUE => "TRUE".to_string(),
@@ -72,10 +92,56 @@ impl Display for Token {
     }
 }
 
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    pub fn start_position(&self, input: &[u8]) -> Position {
+        Position::at(input, self.start)
+    }
+
+    pub fn end_position(&self, input: &[u8]) -> Position {
+        Position::at(input, self.end)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Position {
+    pub row: u32,
+    pub col: u32,
+}
+
+impl Position {
+    pub fn at(input: &[u8], offset: usize) -> Self {
+        let offset = offset.min(input.len());
+        let preceding = &input[..offset];
+        let row = preceding.iter().filter(|&&ch| is_newline(ch)).count();
+        let line_start = preceding
+            .iter()
+            .rposition(|&ch| is_newline(ch))
+            .map_or(0, |i| i + 1);
+        Self {
+            row: row as u32,
+            col: (offset - line_start) as u32,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct TokenWithMeta {
     pub token: Token,
-    pub pos: usize,
+    pub span: Span,
     pub row_pos: u32,
     pub col_pos: u32,
 }
@@ -86,11 +152,13 @@ pub struct Lexer<'a> {
     read_position: usize,
     ch: u8,
 
-    token_pos: usize,
-    token_row_pos: u32,
-    token_col_pos: u32,
-    token_row_pos_delta: u32,
-    token_col_pos_delta: u32,
+    row_pos: u32,
+    col_pos: u32,
+    // Where the last token ended, which is where EOF is reported.
+    last_end: usize,
+    last_end_position: Position,
+    done: bool,
+    preserve_comments: bool,
 }
 
 impl<'a> Lexer<'a> {
@@ -101,15 +169,32 @@ impl<'a> Lexer<'a> {
             read_position: 0,
             ch: 0,
 
-            token_pos: 0,
-            token_row_pos: 0,
-            token_col_pos: 0,
-            token_row_pos_delta: 0,
-            token_col_pos_delta: 0,
+            row_pos: 0,
+            col_pos: 0,
+            last_end: 0,
+            last_end_position: Position::default(),
+            done: false,
+            preserve_comments: false,
         }
     }
 
+    // Emit comments as `Token::COMMENT` instead of skipping them, for tools
+    // such as formatters that need to keep them. The parser ignores them.
+    pub fn preserve_comments(mut self, preserve: bool) -> Self {
+        self.preserve_comments = preserve;
+        self
+    }
+
     pub fn read_char(&mut self) {
+        if self.read_position > 0 && self.read_position <= self.input.len() {
+            if is_newline(self.ch) {
+                self.row_pos += 1;
+                self.col_pos = 0;
+            } else {
+                self.col_pos += 1;
+            }
+        }
+
         let size = self.input.len();
         self.ch = if self.read_position >= size {
             0
@@ -118,12 +203,6 @@ impl<'a> Lexer<'a> {
         };
         self.position = self.read_position;
         self.read_position += 1;
-        self.token_col_pos_delta += 1;
-
-        if is_newline(self.ch) {
-            self.token_col_pos_delta = 0;
-            self.token_row_pos_delta += 1;
-        }
     }
 
     pub fn peek_char(&self) -> u8 {
@@ -138,37 +217,165 @@ impl<'a> Lexer<'a> {
         if self.position == 0 && self.read_position == 0 {
             self.read_char();
         }
-        self.eat_whitespace();
-        let token = match self.ch {
-            ch if is_letter(ch) => match self.read_string() {
-                "let" => Token::LET,
-                "fn" => Token::FUNCTION,
-                "true" => Token::TRUE,
-                "false" => Token::FALSE,
-                "if" => Token::IF,
-                "else" => Token::ELSE,
-                "return" => Token::RETURN,
-                string => Token::IDENT(string.to_string()),
-            },
-            ch if is_digit(ch) => Token::INT(self.read_int()),
-            _ => self.match_char(),
-        };
+        loop {
+            self.eat_whitespace();
+            let start = self.position.min(self.input.len());
+            let row_pos = self.row_pos;
+            let col_pos = self.col_pos;
+            let token = match self.ch {
+                b'/' if self.peek_char() == b'/' => self.read_line_comment(),
+                // A `#!` line at the very start lets scripts be executable.
+                b'#' if start == 0 && self.peek_char() == b'!' => self.read_line_comment(),
+                b'/' if self.peek_char() == b'*' => self.read_block_comment(),
+                ch if is_letter(ch) => {
+                    let string = self.read_string();
+                    lookup_keyword(string).unwrap_or_else(|| Token::IDENT(string.to_string()))
+                }
+                ch if is_digit(ch) => self.read_int(),
+                b'"' => self.read_string_literal(),
+                _ => self.match_char(),
+            };
+            // EOF sits right after the last token rather than after trailing
+            // whitespace, so diagnostics about it point at the code that was
+            // cut short.
+            if token == Token::EOF {
+                return TokenWithMeta {
+                    token,
+                    span: Span::new(self.last_end, self.last_end),
+                    row_pos: self.last_end_position.row,
+                    col_pos: self.last_end_position.col,
+                };
+            }
+            let end = self.position.min(self.input.len());
+            self.last_end = end;
+            self.last_end_position = Position {
+                row: self.row_pos,
+                col: self.col_pos,
+            };
+            if matches!(token, Token::COMMENT(_)) && !self.preserve_comments {
+                continue;
+            }
+
+            return TokenWithMeta {
+                token,
+                span: Span::new(start, end),
+                row_pos,
+                col_pos,
+            };
+        }
+    }
 
-        let token_pos = self.token_pos;
-        let token_row_pos = self.token_row_pos;
-        let token_col_pos = self.token_col_pos;
-        self.token_pos = self.position;
-        self.token_row_pos += self.token_row_pos_delta;
-        self.token_col_pos += self.token_col_pos_delta;
-        self.token_row_pos_delta = 0;
-        self.token_col_pos_delta = 0;
+    fn at_end(&self) -> bool {
+        self.position >= self.input.len()
+    }
 
-        TokenWithMeta {
-            token,
-            pos: token_pos,
-            row_pos: token_row_pos,
-            col_pos: token_col_pos,
+    fn read_line_comment(&mut self) -> Token {
+        let start = self.position;
+        while !self.at_end() && !is_newline(self.ch) {
+            self.read_char();
         }
+        Token::COMMENT(self.text_from(start))
+    }
+
+    // Block comments nest, so `/* a /* b */ c */` is a single comment. One
+    // left open at the end of the input becomes an ILLEGAL token spanning the
+    // rest of the input.
+    fn read_block_comment(&mut self) -> Token {
+        let start = self.position;
+        let mut depth = 0;
+        loop {
+            if self.at_end() {
+                return Token::ILLEGAL(IllegalKind::UnterminatedComment, self.text_from(start));
+            }
+            match (self.ch, self.peek_char()) {
+                (b'/', b'*') => {
+                    depth += 1;
+                    self.read_char();
+                }
+                (b'*', b'/') => {
+                    depth -= 1;
+                    self.read_char();
+                    if depth == 0 {
+                        self.read_char();
+                        return Token::COMMENT(self.text_from(start));
+                    }
+                }
+                _ => {}
+            }
+            self.read_char();
+        }
+    }
+
+    // Reads a double-quoted string, decoding `\n`, `\t`, `\"`, `\\` and
+    // `\u{...}` escapes. A string with an unknown escape is still read up to
+    // its closing quote so that only the literal itself is reported.
+    fn read_string_literal(&mut self) -> Token {
+        let start = self.position;
+        let mut bytes = Vec::new();
+        let mut valid = true;
+        self.read_char();
+        loop {
+            if self.at_end() {
+                return Token::ILLEGAL(IllegalKind::UnterminatedString, self.text_from(start));
+            }
+            match self.ch {
+                b'"' => break,
+                b'\\' => {
+                    self.read_char();
+                    match self.read_escape() {
+                        Some(ch) => {
+                            let mut buffer = [0; 4];
+                            bytes.extend_from_slice(ch.encode_utf8(&mut buffer).as_bytes());
+                        }
+                        None => valid = false,
+                    }
+                }
+                ch => {
+                    bytes.push(ch);
+                    self.read_char();
+                }
+            }
+        }
+        self.read_char();
+        if valid {
+            Token::STRING(String::from_utf8_lossy(&bytes).into_owned())
+        } else {
+            Token::ILLEGAL(IllegalKind::InvalidEscape, self.text_from(start))
+        }
+    }
+
+    // Expects the current character to follow a backslash and leaves the
+    // lexer on the character after the escape.
+    fn read_escape(&mut self) -> Option<char> {
+        let escaped = match self.ch {
+            b'n' => '\n',
+            b't' => '\t',
+            b'"' => '"',
+            b'\\' => '\\',
+            b'u' if self.peek_char() == b'{' => {
+                self.read_char();
+                self.read_char();
+                let start = self.position;
+                while self.ch.is_ascii_hexdigit() {
+                    self.read_char();
+                }
+                let digits = std::str::from_utf8(&self.input[start..self.position]).ok()?;
+                if self.ch != b'}' || digits.is_empty() || digits.len() > 6 {
+                    return None;
+                }
+                u32::from_str_radix(digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)?
+            }
+            _ => return None,
+        };
+        self.read_char();
+        Some(escaped)
+    }
+
+    fn text_from(&self, start: usize) -> String {
+        let end = self.position.min(self.input.len());
+        String::from_utf8_lossy(&self.input[start..end]).into_owned()
     }
 
     fn eat_whitespace(&mut self) {
@@ -186,14 +393,39 @@ impl<'a> Lexer<'a> {
             .expect("failed to convert letter bytes to &str {err}")
     }
 
-    fn read_int(&mut self) -> i32 {
+    fn read_int(&mut self) -> Token {
         let start = self.position;
         while is_digit(self.ch) {
             self.read_char();
         }
         let string = std::str::from_utf8(&self.input[start..self.position])
             .expect("failed to convert slice of digit bytes to string");
-        string.parse().expect("failed to convert &str to i32")
+        match string.parse() {
+            Ok(number) => Token::INT(number),
+            Err(_) => Token::ILLEGAL(IllegalKind::IntegerOverflow, string.to_string()),
+        }
+    }
+
+    // Takes the whole UTF-8 sequence so that the token names the character
+    // as written and its span never ends inside it. Bytes that aren't valid
+    // UTF-8 are reported one at a time.
+    fn read_illegal_char(&mut self) -> Token {
+        let rest = &self.input[self.position..];
+        let rest = &rest[..rest.len().min(4)];
+        let valid = match std::str::from_utf8(rest) {
+            Ok(valid) => valid,
+            Err(err) => std::str::from_utf8(&rest[..err.valid_up_to()])
+                .expect("failed to convert valid UTF-8 prefix to &str"),
+        };
+        match valid.chars().next() {
+            Some(ch) => {
+                for _ in 1..ch.len_utf8() {
+                    self.read_char();
+                }
+                Token::ILLEGAL(IllegalKind::Character, ch.to_string())
+            }
+            None => Token::ILLEGAL(IllegalKind::Character, format!("\\x{:02X}", self.ch)),
+        }
     }
 
     fn match_char(&mut self) -> Token {
@@ -216,28 +448,62 @@ impl<'a> Lexer<'a> {
             b'(' => Token::LPAREN,
             b')' => Token::RPAREN,
             b',' => Token::COMMA,
+            b':' => Token::COLON,
             b'+' => Token::PLUS,
             b'-' => Token::MINUS,
             b'{' => Token::LBRACE,
             b'}' => Token::RBRACE,
+            b'[' => Token::LBRACKET,
+            b']' => Token::RBRACKET,
             b'*' => Token::ASTERISK,
             b'/' => Token::SLASH,
             b'<' => Token::LT,
             b'>' => Token::GT,
             0 => Token::EOF,
-            _ => Token::ILLEGAL,
+            _ => self.read_illegal_char(),
         };
         self.read_char();
         token
     }
 }
 
+// Yields every token up to and including the first EOF.
+impl Iterator for Lexer<'_> {
+    type Item = TokenWithMeta;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let token = self.next_token();
+        self.done = token.token == Token::EOF;
+        Some(token)
+    }
+}
+
+pub const KEYWORDS: &[(&str, Token)] = &[
+    ("let", Token::LET),
+    ("fn", Token::FUNCTION),
+    ("true", Token::TRUE),
+    ("false", Token::FALSE),
+    ("if", Token::IF),
+    ("else", Token::ELSE),
+    ("return", Token::RETURN),
+];
+
+pub fn lookup_keyword(ident: &str) -> Option<Token> {
+    KEYWORDS
+        .iter()
+        .find(|(keyword, _)| *keyword == ident)
+        .map(|(_, token)| token.clone())
+}
+
 pub fn is_letter(ch: u8) -> bool {
-    b'a' <= ch && ch <= b'z' || b'A' <= ch && ch <= b'Z' || ch == b'_'
+    ch.is_ascii_lowercase() || ch.is_ascii_uppercase() || ch == b'_'
 }
 
 pub fn is_digit(ch: u8) -> bool {
-    b'0' <= ch && ch <= b'9'
+    ch.is_ascii_digit()
 }
 
 pub fn is_whitespace(ch: u8) -> bool {
@@ -1,3 +1,11 @@
 pub mod ast;
+pub mod builtins;
+pub mod diagnostics;
+pub mod editor;
+pub mod environment;
+pub mod evaluator;
 pub mod lexer;
+pub mod object;
 pub mod parser;
+pub mod repl;
+pub mod runner;
@@ -1,25 +1,78 @@
-mod ast;
-mod lexer;
-mod parser;
+use monkey_interpreter::editor;
+use monkey_interpreter::object::Object;
+use monkey_interpreter::repl::Repl;
+use monkey_interpreter::runner::{self, EXIT_NO_INPUT, EXIT_USAGE};
+use std::io::{self, IsTerminal, Read};
+use std::process::ExitCode;
 
-use lexer::Lexer;
-use parser::Parser;
-use std::io;
+const USAGE: &str = "\
+usage: monkey_interpreter               start the REPL, or run a program piped to stdin
+       monkey_interpreter run <file>    run the program in <file>
+       monkey_interpreter -e <source>   run <source> and print its value";
 
-fn main() {
-    println!("Lexer REPL started");
-    loop {
-        let mut line = String::new();
-        io::stdin()
-            .read_line(&mut line)
-            .expect("Failed to read line");
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        [] if io::stdin().is_terminal() => run_repl(),
+        [] => {
+            let mut source = String::new();
+            match io::stdin().read_to_string(&mut source) {
+                Ok(_) => run_program(&source, false),
+                Err(err) => {
+                    eprintln!("error: cannot read stdin: {err}");
+                    ExitCode::from(EXIT_NO_INPUT)
+                }
+            }
+        }
+        ["run", path] => match std::fs::read_to_string(path) {
+            Ok(source) => run_program(&source, false),
+            Err(err) => {
+                eprintln!("error: cannot read {path}: {err}");
+                ExitCode::from(EXIT_NO_INPUT)
+            }
+        },
+        ["-e", source] => run_program(source, true),
+        ["-h" | "--help"] => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::from(EXIT_USAGE)
+        }
+    }
+}
+
+fn run_repl() -> ExitCode {
+    let mut repl = Repl::new(io::stdout().is_terminal());
+    println!("Monkey REPL, press Ctrl-D to exit");
+    match editor::run(&mut repl) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
 
-        let mut lexer = Lexer::new(line.as_bytes());
-        let mut parser = Parser::new(&mut lexer);
-        let program = parser.parse_program();
-        match program {
-            Ok(program) => println!("{program}"),
-            Err(errors) => println!("errors"), // dbg!(errors),
-        };
+// Diagnostics go to stderr so that only the program's own output reaches
+// stdout. One-liners also print their value.
+fn run_program(source: &str, print_result: bool) -> ExitCode {
+    match runner::run(source) {
+        Ok(Object::Null) => ExitCode::SUCCESS,
+        Ok(object) => {
+            if print_result {
+                println!("{object}");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(failure) => {
+            let color = io::stderr().is_terminal();
+            for diagnostic in failure.diagnostics() {
+                eprintln!("{}", diagnostic.render(source, color));
+            }
+            ExitCode::from(failure.exit_code())
+        }
     }
 }
@@ -0,0 +1,148 @@
+use crate::ast;
+use crate::environment::Env;
+use crate::evaluator::RuntimeError;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Object {
+    Integer(i32),
+    Boolean(bool),
+    String(String),
+    Array(Vec<Object>),
+    Hash(BTreeMap<HashKey, Object>),
+    Null,
+    Function(Rc<Function>),
+    Builtin(Builtin),
+}
+
+impl Object {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Integer(_) => "INTEGER",
+            Self::Boolean(_) => "BOOLEAN",
+            Self::String(_) => "STRING",
+            Self::Array(_) => "ARRAY",
+            Self::Hash(_) => "HASH",
+            Self::Null => "NULL",
+            Self::Function(_) => "FUNCTION",
+            Self::Builtin(_) => "BUILTIN",
+        }
+    }
+
+    // Nested strings are quoted so `["a, b"]` and `["a", "b"]` print
+    // differently.
+    pub fn inspect(&self) -> String {
+        match self {
+            Self::String(s) => format!("{s:?}"),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl Display for Object {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Boolean(b) => write!(f, "{b}"),
+            Self::String(s) => write!(f, "{s}"),
+            Self::Array(elements) => {
+                let elements: Vec<String> = elements.iter().map(Self::inspect).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Self::Hash(pairs) => {
+                let pairs: Vec<String> = pairs
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value.inspect()))
+                    .collect();
+                write!(f, "{{{}}}", pairs.join(", "))
+            }
+            Self::Null => write!(f, "null"),
+            Self::Function(function) => write!(f, "{function}"),
+            Self::Builtin(builtin) => write!(f, "{builtin}"),
+        }
+    }
+}
+
+// The values that can be used as hash keys. Keys are kept ordered, so hashes
+// always print the same way.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub enum HashKey {
+    Integer(i32),
+    Boolean(bool),
+    String(String),
+}
+
+impl HashKey {
+    pub fn from_object(object: &Object) -> Option<Self> {
+        match object {
+            Object::Integer(n) => Some(Self::Integer(*n)),
+            Object::Boolean(b) => Some(Self::Boolean(*b)),
+            Object::String(s) => Some(Self::String(s.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl Display for HashKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Boolean(b) => write!(f, "{b}"),
+            Self::String(s) => write!(f, "{s:?}"),
+        }
+    }
+}
+
+pub struct Function {
+    pub params: Vec<ast::Identifier>,
+    pub body: Rc<ast::BlockStatement>,
+    pub env: Env,
+}
+
+// The captured environment usually contains the function itself, so it is
+// left out of `Debug` and functions compare by identity.
+impl std::fmt::Debug for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Function")
+            .field("params", &self.params)
+            .field("body", &self.body)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<&str> = self.params.iter().map(|p| p.name.as_str()).collect();
+        write!(f, "fn({}) {{ ... }}", params.join(", "))
+    }
+}
+
+pub type BuiltinFn = fn(&[Object]) -> Result<Object, RuntimeError>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Builtin {
+    pub name: &'static str,
+    pub func: BuiltinFn,
+}
+
+// Every builtin has a unique name, which is more reliable to compare than
+// function pointers.
+impl PartialEq for Builtin {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Display for Builtin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "builtin {}", self.name)
+    }
+}
@@ -1,78 +1,287 @@
 use crate::ast;
-use crate::lexer::{Lexer, Token, TokenWithMeta};
+use crate::lexer::{IllegalKind, Lexer, Span, Token, TokenWithMeta};
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseErrorKind {
+    UnexpectedToken,
+    UnexpectedEof,
+    InvalidLiteral,
+    UnterminatedComment,
+    UnterminatedString,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expected {
+    Token(Token),
+    Identifier,
+    Integer,
+    String,
+    Expression,
+    Operator,
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Token(token) => write!(f, "{token}"),
+            Self::Identifier => write!(f, "IDENT"),
+            Self::Integer => write!(f, "INT"),
+            Self::String => write!(f, "STRING"),
+            Self::Expression => write!(f, "expression"),
+            Self::Operator => write!(f, "operator"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub expected: Vec<Expected>,
+    pub found: TokenWithMeta,
+    pub span: Span,
+}
+
+impl ParseError {
+    pub fn new(expected: Vec<Expected>, found: TokenWithMeta) -> Self {
+        let kind = match &found.token {
+            Token::EOF => ParseErrorKind::UnexpectedEof,
+            Token::ILLEGAL(IllegalKind::IntegerOverflow | IllegalKind::InvalidEscape, _) => {
+                ParseErrorKind::InvalidLiteral
+            }
+            Token::ILLEGAL(IllegalKind::UnterminatedComment, _) => {
+                ParseErrorKind::UnterminatedComment
+            }
+            Token::ILLEGAL(IllegalKind::UnterminatedString, _) => {
+                ParseErrorKind::UnterminatedString
+            }
+            _ => ParseErrorKind::UnexpectedToken,
+        };
+        Self {
+            kind,
+            expected,
+            span: found.span,
+            found,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self.kind {
+            ParseErrorKind::InvalidLiteral => format!("invalid literal {}", self.found.token),
+            ParseErrorKind::UnterminatedComment => "unterminated block comment".to_string(),
+            ParseErrorKind::UnterminatedString => "unterminated string literal".to_string(),
+            _ => format!(
+                "expected {}, got {}",
+                self.expected_list(),
+                self.found.token
+            ),
+        }
+    }
+
+    pub fn expected_list(&self) -> String {
+        let expected: Vec<String> = self.expected.iter().map(|e| e.to_string()).collect();
+        expected.join(" or ")
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}:{}] {}",
+            self.found.row_pos,
+            self.found.col_pos,
+            self.message()
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn parse_str(input: &str) -> Result<ast::Program, Vec<ParseError>> {
+    Parser::new(Lexer::new(input.as_bytes())).parse_program()
+}
 
-pub struct Parser<'a> {
-    lexer: &'a mut Lexer<'a>,
+pub struct Parser<I: Iterator<Item = TokenWithMeta>> {
+    tokens: I,
     cur_token: TokenWithMeta,
     peek_token: TokenWithMeta,
+    // Number of braces opened before `cur_token` and not yet closed.
+    depth: usize,
+    errors: Vec<ParseError>,
+    // Set when a statement recovered from an error and already skipped to
+    // the start of the next one.
+    recovered: bool,
 }
 
-impl<'a> Parser<'a> {
-    pub fn new(lexer: &'a mut Lexer<'a>) -> Self {
-        let cur_token = lexer.next_token();
-        let peek_token = lexer.next_token();
+impl<I: Iterator<Item = TokenWithMeta>> Parser<I> {
+    // Any token source works; once it runs out the parser behaves as if it
+    // ended with an EOF token.
+    pub fn new(mut tokens: I) -> Self {
+        let cur_token = next_significant(&mut tokens, None);
+        let peek_token = next_significant(&mut tokens, Some(&cur_token));
         Parser {
-            lexer,
+            tokens,
             cur_token,
             peek_token,
+            depth: 0,
+            errors: Vec::new(),
+            recovered: false,
+        }
+    }
+
+    pub fn parse_program(&mut self) -> Result<ast::Program, Vec<ParseError>> {
+        let (program, errors) = self.parse_program_partial();
+        if errors.is_empty() {
+            Ok(program)
+        } else {
+            Err(errors)
         }
     }
 
-    pub fn parse_program(&mut self) -> Result<ast::Program, Vec<String>> {
+    // Always produces a program: statements and `let`/`return` values that
+    // failed to parse are kept as `Error` nodes spanning the skipped tokens.
+    pub fn parse_program_partial(&mut self) -> (ast::Program, Vec<ParseError>) {
         let mut statements: Vec<ast::Statement> = Vec::new();
-        let mut errors: Vec<String> = Vec::new();
         while self.cur_token.token != Token::EOF {
-            match self.parse_statement() {
-                Ok(stmt) => {
-                    statements.push(stmt);
+            statements.push(self.parse_statement_or_recover());
+        }
+        (
+            ast::Program { statements },
+            std::mem::take(&mut self.errors),
+        )
+    }
+
+    // Parses a statement and moves past it. On failure the error is recorded
+    // and tokens are skipped until the next statement boundary, so that one
+    // bad statement does not hide errors in the ones after it.
+    fn parse_statement_or_recover(&mut self) -> ast::Statement {
+        let start = self.cur_token.span;
+        let depth = self.depth;
+        match self.parse_statement() {
+            Ok(stmt) => {
+                if !std::mem::take(&mut self.recovered) {
                     self.advance_tokens();
                 }
-                Err(err) => {
-                    errors.push(err);
-                    self.skip_statement();
-                }
-            };
+                stmt
+            }
+            Err(err) => {
+                self.errors.push(err);
+                self.recovered = false;
+                let skipped = self.synchronize(depth, start);
+                ast::Statement::new(ast::StatementKind::Error, error_span(start, skipped))
+            }
         }
-        if errors.len() == 0 {
-            Ok(ast::Program { statements })
-        } else {
-            Err(errors)
+    }
+
+    fn parse_expression_or_recover(&mut self, depth: usize, stmt_start: Span) -> ast::Expression {
+        let start = self.cur_token.span;
+        match self.parse_expression(Precedence::Lowest) {
+            Ok(expr) => expr,
+            Err(err) => {
+                self.errors.push(err);
+                let skipped = self.synchronize(depth, stmt_start);
+                self.recovered = true;
+                ast::Expression::new(ast::ExpressionKind::Error, error_span(start, skipped))
+            }
         }
     }
 
-    fn skip_statement(&mut self) {
-        while self.cur_token.token != Token::SEMICOLON && self.cur_token.token != Token::EOF {
-            self.advance_tokens();
+    // Statement boundaries only count at the brace depth the failed
+    // statement started at: a semicolon there is consumed, while a statement
+    // keyword or the closing brace of the enclosing block is left for the
+    // caller. Anything nested deeper is skipped as a whole.
+    // Returns the span of the last skipped token, not counting the
+    // consumed semicolon.
+    fn synchronize(&mut self, depth: usize, start: Span) -> Option<Span> {
+        let mut skipped = None;
+        loop {
+            let at_depth = self.depth == depth;
+            let moved = self.cur_token.span != start;
+            match self.cur_token.token {
+                Token::EOF => return skipped,
+                Token::SEMICOLON if at_depth => {
+                    self.advance_tokens();
+                    return skipped;
+                }
+                Token::RBRACE if at_depth && depth > 0 => return skipped,
+                Token::LET | Token::RETURN | Token::IF | Token::FUNCTION if at_depth && moved => {
+                    return skipped
+                }
+                _ => {
+                    skipped = Some(self.cur_token.span);
+                    self.advance_tokens();
+                }
+            }
         }
-        self.advance_tokens();
     }
 
     fn advance_tokens(&mut self) {
-        self.cur_token = self.peek_token.clone();
-        self.peek_token = self.lexer.next_token();
+        match self.cur_token.token {
+            Token::LBRACE => self.depth += 1,
+            Token::RBRACE => self.depth = self.depth.saturating_sub(1),
+            _ => {}
+        }
+        let next_token = next_significant(&mut self.tokens, Some(&self.peek_token));
+        self.cur_token = std::mem::replace(&mut self.peek_token, next_token);
     }
 
-    fn check_cur_token(&self, token: Token) -> Result<bool, String> {
+    fn check_cur_token(&self, token: Token) -> Result<bool, ParseError> {
         if self.cur_token.token == token {
             Ok(true)
         } else {
-            Err(format_error(&format!("{}", token), &self.cur_token))
+            Err(format_error(vec![Expected::Token(token)], &self.cur_token))
         }
     }
 
-    fn parse_statement(&mut self) -> Result<ast::Statement, String> {
+    // Spans run from `start` to the end of the current token, which is the
+    // last token of the construct once its parse function returns.
+    fn span_from(&self, start: Span) -> Span {
+        start.to(self.cur_token.span)
+    }
+
+    fn parse_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let start = self.cur_token.span;
+        let depth = self.depth;
         let stmt = match self.cur_token.token {
             Token::LET => self.parse_let(),
             Token::RETURN => self.parse_statement_return(),
-            Token::IF => self.parse_statement_if(),
             _ => self.parse_statement_expression(),
         }?;
-        self.advance_tokens();
-        self.check_cur_token(Token::SEMICOLON)?;
+        if self.recovered {
+            return Ok(stmt);
+        }
+        // The closing semicolon may be omitted before the end of a block or
+        // the input, and after a statement-level `if`.
+        let ends_with_block = matches!(
+            &stmt.kind,
+            ast::StatementKind::Expression(ast::Expression {
+                kind: ast::ExpressionKind::If { .. },
+                ..
+            })
+        );
+        match self.peek_token.token {
+            Token::SEMICOLON => self.advance_tokens(),
+            Token::RBRACE | Token::EOF => {}
+            _ if ends_with_block => {}
+            _ => {
+                // The statement itself is complete, so keep it and only
+                // report the missing semicolon.
+                self.advance_tokens();
+                if let Err(err) = self.check_cur_token(Token::SEMICOLON) {
+                    self.errors.push(err);
+                    self.synchronize(depth, start);
+                    self.recovered = true;
+                }
+            }
+        }
         Ok(stmt)
     }
 
-    fn parse_let(&mut self) -> Result<ast::Statement, String> {
+    fn parse_let(&mut self) -> Result<ast::Statement, ParseError> {
+        let start = self.cur_token.span;
+        let depth = self.depth;
         self.check_cur_token(Token::LET)?;
 
         self.advance_tokens();
@@ -82,134 +291,477 @@ impl<'a> Parser<'a> {
         self.check_cur_token(Token::ASSIGN)?;
 
         self.advance_tokens();
-        let expr = self.parse_expression()?;
+        let expr = self.parse_expression_or_recover(depth, start);
 
-        Ok(ast::Statement::Let(ident, expr))
+        let span = start.to(expr.span);
+        Ok(ast::Statement::new(
+            ast::StatementKind::Let(ident, expr),
+            span,
+        ))
     }
 
-    fn parse_statement_return(&mut self) -> Result<ast::Statement, String> {
+    fn parse_statement_return(&mut self) -> Result<ast::Statement, ParseError> {
+        let start = self.cur_token.span;
+        let depth = self.depth;
         self.check_cur_token(Token::RETURN)?;
         self.advance_tokens();
-        let expr = self.parse_expression()?;
-        Ok(ast::Statement::Return(expr))
+        let expr = self.parse_expression_or_recover(depth, start);
+        let span = start.to(expr.span);
+        Ok(ast::Statement::new(ast::StatementKind::Return(expr), span))
     }
 
-    fn parse_statement_expression(&mut self) -> Result<ast::Statement, String> {
-        let expr = self.parse_expression()?;
-        Ok(ast::Statement::Expression(expr))
+    fn parse_statement_expression(&mut self) -> Result<ast::Statement, ParseError> {
+        let expr = self.parse_expression(Precedence::Lowest)?;
+        let span = expr.span;
+        Ok(ast::Statement::new(
+            ast::StatementKind::Expression(expr),
+            span,
+        ))
     }
 
-    fn parse_statement_if(&mut self) -> Result<ast::Statement, String> {
+    fn parse_expression_if(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.cur_token.span;
         self.check_cur_token(Token::IF)?;
 
         self.advance_tokens();
         self.check_cur_token(Token::LPAREN)?;
         self.advance_tokens();
-        let expr = self.parse_expression()?;
+        let condition = self.parse_expression(Precedence::Lowest)?;
         self.advance_tokens();
         self.check_cur_token(Token::RPAREN)?;
 
         self.advance_tokens();
-        self.check_cur_token(Token::LBRACE)?;
-        self.advance_tokens();
-        let stmt_1 = self.parse_statement()?;
-        self.advance_tokens();
-        self.check_cur_token(Token::RBRACE)?;
+        let consequence = self.parse_block_statement()?;
+
+        let alternative = match self.peek_token.token {
+            Token::ELSE => {
+                self.advance_tokens();
+                self.advance_tokens();
+                Some(self.parse_else_branch()?)
+            }
+            _ => None,
+        };
 
-        self.advance_tokens();
-        self.check_cur_token(Token::ELSE)?;
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::If {
+                condition: Box::new(condition),
+                consequence,
+                alternative,
+            },
+            self.span_from(start),
+        ))
+    }
 
-        self.advance_tokens();
+    // `else if` is sugar for an `else` block holding a single `if` expression.
+    fn parse_else_branch(&mut self) -> Result<ast::BlockStatement, ParseError> {
+        match self.cur_token.token {
+            Token::IF => {
+                let expr = self.parse_expression_if()?;
+                let span = expr.span;
+                Ok(ast::BlockStatement {
+                    statements: vec![ast::Statement::new(
+                        ast::StatementKind::Expression(expr),
+                        span,
+                    )],
+                    span,
+                })
+            }
+            _ => self.parse_block_statement(),
+        }
+    }
+
+    fn parse_block_statement(&mut self) -> Result<ast::BlockStatement, ParseError> {
+        let start = self.cur_token.span;
         self.check_cur_token(Token::LBRACE)?;
+
+        let mut statements = Vec::new();
         self.advance_tokens();
-        let stmt_2 = self.parse_statement()?;
-        self.advance_tokens();
-        self.check_cur_token(Token::RBRACE)?;
+        while self.cur_token.token != Token::RBRACE {
+            if self.cur_token.token == Token::EOF {
+                return Err(format_error(
+                    vec![Expected::Token(Token::RBRACE)],
+                    &self.cur_token,
+                ));
+            }
+            statements.push(self.parse_statement_or_recover());
+        }
 
-        Ok(ast::Statement::If(expr, Box::new(stmt_1), Box::new(stmt_2)))
+        Ok(ast::BlockStatement {
+            statements,
+            span: self.span_from(start),
+        })
     }
 
-    fn parse_identifier(&mut self) -> Result<ast::Identifier, String> {
+    fn parse_identifier(&mut self) -> Result<ast::Identifier, ParseError> {
         match self.cur_token.token.clone() {
-            Token::IDENT(name) => Ok(ast::Identifier { name }),
-            _ => Err(format_error("IDENT", &self.cur_token)),
+            Token::IDENT(name) => Ok(ast::Identifier {
+                name,
+                span: self.cur_token.span,
+            }),
+            _ => Err(format_error(vec![Expected::Identifier], &self.cur_token)),
         }
     }
 
-    fn parse_expression(&mut self) -> Result<ast::Expression, String> {
-        let maybe_expr = match self.cur_token.token {
-            Token::LPAREN => self.parse_expression_grouped(),
-            _ => match self.peek_token.token {
-                Token::PLUS | Token::MINUS => self.parse_expression_binary(),
-                Token::SEMICOLON | Token::RPAREN => self.parse_expression_unary(),
-                _ => Err(format_error("binary or unary expression", &self.cur_token)),
-            },
-        };
-        maybe_expr
+    fn parse_expression(&mut self, precedence: Precedence) -> Result<ast::Expression, ParseError> {
+        let prefix = prefix_parse_fn(&self.cur_token.token)
+            .ok_or_else(|| format_error(vec![Expected::Expression], &self.cur_token))?;
+        let mut left = prefix(self)?;
+
+        while self.peek_token.token != Token::SEMICOLON
+            && precedence < token_precedence(&self.peek_token.token)
+        {
+            let infix = match infix_parse_fn(&self.peek_token.token) {
+                Some(infix) => infix,
+                None => return Ok(left),
+            };
+            self.advance_tokens();
+            left = infix(self, left)?;
+        }
+        Ok(left)
     }
 
-    fn parse_expression_binary(&mut self) -> Result<ast::Expression, String> {
-        let expr_1 = self.parse_expression_unary()?;
+    fn parse_expression_prefix(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.cur_token.span;
+        let operator = match self.cur_token.token {
+            Token::BANG => ast::PrefixOperator::Not,
+            Token::MINUS => ast::PrefixOperator::Negate,
+            _ => {
+                return Err(format_error(
+                    vec![Expected::Token(Token::BANG), Expected::Token(Token::MINUS)],
+                    &self.cur_token,
+                ))
+            }
+        };
 
         self.advance_tokens();
+        let expr = self.parse_expression(Precedence::Prefix)?;
+
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::Prefix(operator, Box::new(expr)),
+            self.span_from(start),
+        ))
+    }
+
+    fn parse_expression_binary(
+        &mut self,
+        left: ast::Expression,
+    ) -> Result<ast::Expression, ParseError> {
         let operator = self.parse_operator()?;
+        let precedence = token_precedence(&self.cur_token.token);
 
         self.advance_tokens();
-        let expr_2 = self.parse_expression()?;
+        let right = self.parse_expression(precedence)?;
 
-        Ok(ast::Expression::Binary(
-            operator,
-            Box::new(expr_1),
-            Box::new(expr_2),
+        let span = left.span.to(right.span);
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::Binary(operator, Box::new(left), Box::new(right)),
+            span,
         ))
     }
 
-    fn parse_expression_unary(&mut self) -> Result<ast::Expression, String> {
+    fn parse_expression_int_literal(&mut self) -> Result<ast::Expression, ParseError> {
         match self.cur_token.token {
-            Token::INT(_) => self.parse_expression_int_literal(),
-            Token::IDENT(_) => self.parse_expression_variable(),
-            _ => Err(format_error("unary expression", &self.cur_token)),
+            Token::INT(number) => Ok(ast::Expression::new(
+                ast::ExpressionKind::IntLiteral(number),
+                self.cur_token.span,
+            )),
+            _ => Err(format_error(vec![Expected::Integer], &self.cur_token)),
         }
     }
 
-    fn parse_expression_int_literal(&mut self) -> Result<ast::Expression, String> {
-        match self.cur_token.token {
-            Token::INT(number) => Ok(ast::Expression::IntLiteral(number)),
-            _ => Err(format_error("INT", &self.cur_token)),
+    fn parse_expression_string_literal(&mut self) -> Result<ast::Expression, ParseError> {
+        match self.cur_token.token.clone() {
+            Token::STRING(string) => Ok(ast::Expression::new(
+                ast::ExpressionKind::StringLiteral(string),
+                self.cur_token.span,
+            )),
+            _ => Err(format_error(vec![Expected::String], &self.cur_token)),
         }
     }
 
-    fn parse_expression_variable(&mut self) -> Result<ast::Expression, String> {
+    fn parse_expression_bool_literal(&mut self) -> Result<ast::Expression, ParseError> {
+        let value = match self.cur_token.token {
+            Token::TRUE => true,
+            Token::FALSE => false,
+            _ => {
+                return Err(format_error(
+                    vec![Expected::Token(Token::TRUE), Expected::Token(Token::FALSE)],
+                    &self.cur_token,
+                ))
+            }
+        };
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::BoolLiteral(value),
+            self.cur_token.span,
+        ))
+    }
+
+    fn parse_expression_variable(&mut self) -> Result<ast::Expression, ParseError> {
         match self.cur_token.token.clone() {
-            Token::IDENT(name) => Ok(ast::Expression::Variable(name)),
-            _ => Err(format_error("IDENT", &self.cur_token)),
+            Token::IDENT(name) => Ok(ast::Expression::new(
+                ast::ExpressionKind::Variable(name),
+                self.cur_token.span,
+            )),
+            _ => Err(format_error(vec![Expected::Identifier], &self.cur_token)),
         }
     }
 
-    fn parse_expression_grouped(&mut self) -> Result<ast::Expression, String> {
+    fn parse_expression_grouped(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.cur_token.span;
         self.check_cur_token(Token::LPAREN)?;
 
         self.advance_tokens();
-        let expr = self.parse_expression()?;
+        let expr = self.parse_expression(Precedence::Lowest)?;
 
         self.advance_tokens();
         self.check_cur_token(Token::RPAREN)?;
 
-        Ok(ast::Expression::Grouped(Box::new(expr)))
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::Grouped(Box::new(expr)),
+            self.span_from(start),
+        ))
+    }
+
+    fn parse_expression_function(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.cur_token.span;
+        self.check_cur_token(Token::FUNCTION)?;
+
+        self.advance_tokens();
+        self.check_cur_token(Token::LPAREN)?;
+        let mut params = Vec::new();
+        self.advance_tokens();
+        while self.cur_token.token != Token::RPAREN {
+            params.push(self.parse_identifier()?);
+            self.advance_tokens();
+            if self.cur_token.token == Token::COMMA {
+                self.advance_tokens();
+            } else {
+                self.check_cur_token(Token::RPAREN)?;
+            }
+        }
+
+        self.advance_tokens();
+        let body = self.parse_block_statement()?;
+
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::Function {
+                params,
+                body: Rc::new(body),
+            },
+            self.span_from(start),
+        ))
+    }
+
+    fn parse_expression_array(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.cur_token.span;
+        self.check_cur_token(Token::LBRACKET)?;
+        let elements = self.parse_expression_list(Token::RBRACKET)?;
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::ArrayLiteral(elements),
+            self.span_from(start),
+        ))
+    }
+
+    // Blocks only follow `if`, `else` and `fn(...)`, which parse them
+    // directly, so a brace in expression position always opens a hash.
+    fn parse_expression_hash(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.cur_token.span;
+        self.check_cur_token(Token::LBRACE)?;
+
+        let mut pairs = Vec::new();
+        self.advance_tokens();
+        while self.cur_token.token != Token::RBRACE {
+            let key = self.parse_expression(Precedence::Lowest)?;
+            self.advance_tokens();
+            self.check_cur_token(Token::COLON)?;
+
+            self.advance_tokens();
+            let value = self.parse_expression(Precedence::Lowest)?;
+            pairs.push((key, value));
+
+            self.advance_tokens();
+            if self.cur_token.token == Token::COMMA {
+                self.advance_tokens();
+            } else {
+                self.check_cur_token(Token::RBRACE)?;
+            }
+        }
+
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::HashLiteral(pairs),
+            self.span_from(start),
+        ))
+    }
+
+    fn parse_expression_call(
+        &mut self,
+        callee: ast::Expression,
+    ) -> Result<ast::Expression, ParseError> {
+        self.check_cur_token(Token::LPAREN)?;
+        let args = self.parse_expression_list(Token::RPAREN)?;
+
+        let span = self.span_from(callee.span);
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::Call {
+                callee: Box::new(callee),
+                args,
+            },
+            span,
+        ))
+    }
+
+    fn parse_expression_index(
+        &mut self,
+        left: ast::Expression,
+    ) -> Result<ast::Expression, ParseError> {
+        self.check_cur_token(Token::LBRACKET)?;
+
+        self.advance_tokens();
+        let index = self.parse_expression(Precedence::Lowest)?;
+
+        self.advance_tokens();
+        self.check_cur_token(Token::RBRACKET)?;
+
+        let span = self.span_from(left.span);
+        Ok(ast::Expression::new(
+            ast::ExpressionKind::Index {
+                left: Box::new(left),
+                index: Box::new(index),
+            },
+            span,
+        ))
+    }
+
+    // Parses comma-separated expressions after an opening delimiter, up to
+    // and including `end`. A trailing comma is allowed.
+    fn parse_expression_list(&mut self, end: Token) -> Result<Vec<ast::Expression>, ParseError> {
+        let mut items = Vec::new();
+        self.advance_tokens();
+        while self.cur_token.token != end {
+            items.push(self.parse_expression(Precedence::Lowest)?);
+            self.advance_tokens();
+            if self.cur_token.token == Token::COMMA {
+                self.advance_tokens();
+            } else {
+                self.check_cur_token(end.clone())?;
+            }
+        }
+        Ok(items)
     }
 
-    fn parse_operator(&mut self) -> Result<ast::BinaryOperator, String> {
+    fn parse_operator(&mut self) -> Result<ast::BinaryOperator, ParseError> {
         match self.cur_token.token {
             Token::PLUS => Ok(ast::BinaryOperator::Add),
             Token::MINUS => Ok(ast::BinaryOperator::Subtract),
-            _ => Err(format_error("operator", &self.cur_token)),
+            Token::ASTERISK => Ok(ast::BinaryOperator::Multiply),
+            Token::SLASH => Ok(ast::BinaryOperator::Divide),
+            Token::LT => Ok(ast::BinaryOperator::Lt),
+            Token::GT => Ok(ast::BinaryOperator::Gt),
+            Token::EQ => Ok(ast::BinaryOperator::Eq),
+            Token::NEQ => Ok(ast::BinaryOperator::NotEq),
+            _ => Err(format_error(vec![Expected::Operator], &self.cur_token)),
         }
     }
 }
 
-fn format_error(expected: &str, token: &TokenWithMeta) -> String {
-    format!(
-        "[{}:{}] expected {}, got {}",
-        token.row_pos, token.col_pos, expected, token.token,
-    )
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+enum Precedence {
+    Lowest,
+    Equals,
+    LessGreater,
+    Sum,
+    Product,
+    Prefix,
+    Call,
+    Index,
+}
+
+type PrefixParseFn<I> = fn(&mut Parser<I>) -> Result<ast::Expression, ParseError>;
+type InfixParseFn<I> = fn(&mut Parser<I>, ast::Expression) -> Result<ast::Expression, ParseError>;
+
+fn token_precedence(token: &Token) -> Precedence {
+    match token {
+        Token::EQ | Token::NEQ => Precedence::Equals,
+        Token::LT | Token::GT => Precedence::LessGreater,
+        Token::PLUS | Token::MINUS => Precedence::Sum,
+        Token::ASTERISK | Token::SLASH => Precedence::Product,
+        Token::LPAREN => Precedence::Call,
+        Token::LBRACKET => Precedence::Index,
+        _ => Precedence::Lowest,
+    }
+}
+
+fn prefix_parse_fn<I: Iterator<Item = TokenWithMeta>>(token: &Token) -> Option<PrefixParseFn<I>> {
+    match token {
+        Token::INT(_) => Some(Parser::parse_expression_int_literal),
+        Token::STRING(_) => Some(Parser::parse_expression_string_literal),
+        Token::TRUE | Token::FALSE => Some(Parser::parse_expression_bool_literal),
+        Token::IDENT(_) => Some(Parser::parse_expression_variable),
+        Token::LPAREN => Some(Parser::parse_expression_grouped),
+        Token::LBRACKET => Some(Parser::parse_expression_array),
+        Token::LBRACE => Some(Parser::parse_expression_hash),
+        Token::IF => Some(Parser::parse_expression_if),
+        Token::FUNCTION => Some(Parser::parse_expression_function),
+        Token::BANG | Token::MINUS => Some(Parser::parse_expression_prefix),
+        _ => None,
+    }
+}
+
+fn infix_parse_fn<I: Iterator<Item = TokenWithMeta>>(token: &Token) -> Option<InfixParseFn<I>> {
+    match token {
+        Token::PLUS
+        | Token::MINUS
+        | Token::ASTERISK
+        | Token::SLASH
+        | Token::LT
+        | Token::GT
+        | Token::EQ
+        | Token::NEQ => Some(Parser::parse_expression_binary),
+        Token::LPAREN => Some(Parser::parse_expression_call),
+        Token::LBRACKET => Some(Parser::parse_expression_index),
+        _ => None,
+    }
+}
+
+// Skips comment trivia and pads an exhausted token source with EOF.
+fn next_significant<I: Iterator<Item = TokenWithMeta>>(
+    tokens: &mut I,
+    last: Option<&TokenWithMeta>,
+) -> TokenWithMeta {
+    tokens
+        .find(|t| !matches!(t.token, Token::COMMENT(_)))
+        .unwrap_or_else(|| eof_after(last))
+}
+
+// Only the span of a synthesized EOF is exact. Without the source text the
+// line and column where a multi-line token ends are unknown, so it reuses
+// those of the last token; diagnostics are rendered from the span.
+fn eof_after(last: Option<&TokenWithMeta>) -> TokenWithMeta {
+    match last {
+        Some(last) => TokenWithMeta {
+            token: Token::EOF,
+            span: Span::new(last.span.end, last.span.end),
+            row_pos: last.row_pos,
+            col_pos: last.col_pos,
+        },
+        None => TokenWithMeta {
+            token: Token::EOF,
+            span: Span::default(),
+            row_pos: 0,
+            col_pos: 0,
+        },
+    }
+}
+
+// Error nodes cover the tokens skipped while recovering, or are empty at
+// `start` when recovery stopped right away.
+fn error_span(start: Span, skipped: Option<Span>) -> Span {
+    match skipped {
+        Some(skipped) => start.to(skipped),
+        None => Span::new(start.start, start.start),
+    }
+}
+
+fn format_error(expected: Vec<Expected>, token: &TokenWithMeta) -> ParseError {
+    ParseError::new(expected, token.clone())
 }
@@ -0,0 +1,241 @@
+use crate::builtins;
+use crate::diagnostics::Diagnostic;
+use crate::environment::{Env, Environment};
+use crate::evaluator;
+use crate::lexer::{self, IllegalKind, Lexer, Token};
+use crate::object::Object;
+use crate::parser::{self, ParseErrorKind, Parser};
+use std::io::{self, Write};
+
+pub const PROMPT: &str = ">> ";
+pub const CONTINUATION_PROMPT: &str = ".. ";
+
+const HELP: &str = "\
+:tokens <src>  show the tokens of <src>
+:ast <src>     show the syntax tree of <src>
+:env           list the current bindings
+:reset         drop all bindings
+:load <file>   evaluate the contents of <file>
+:help          show this message
+";
+
+// Bindings made on one line stay visible on the following ones. Incomplete
+// input keeps collecting lines under the continuation prompt; an empty line
+// evaluates whatever has been collected so that mistakes get reported.
+// Lines starting with `:` at the main prompt are REPL commands.
+pub struct Repl {
+    env: Env,
+    color: bool,
+    // Lines of the statement being entered.
+    buffer: String,
+}
+
+impl Repl {
+    pub fn new(color: bool) -> Self {
+        Self {
+            env: Environment::new(),
+            color,
+            buffer: String::new(),
+        }
+    }
+
+    pub fn prompt(&self) -> &'static str {
+        if self.buffer.is_empty() {
+            PROMPT
+        } else {
+            CONTINUATION_PROMPT
+        }
+    }
+
+    pub fn feed_line(&mut self, line: &str, output: &mut impl Write) -> io::Result<()> {
+        if self.buffer.is_empty() && line.starts_with(':') {
+            return self.run_command(line.trim(), output);
+        }
+        self.buffer.push_str(line);
+        if !line.ends_with('\n') {
+            self.buffer.push('\n');
+        }
+        if line.trim().is_empty() || is_complete(&self.buffer) {
+            let source = std::mem::take(&mut self.buffer);
+            self.eval_source(&source, output)?;
+        }
+        Ok(())
+    }
+
+    // Evaluates input left over when the input ends.
+    pub fn finish(&mut self, output: &mut impl Write) -> io::Result<()> {
+        let source = std::mem::take(&mut self.buffer);
+        self.eval_source(&source, output)
+    }
+
+    // Drops a partly entered statement.
+    pub fn cancel(&mut self) {
+        self.buffer.clear();
+    }
+
+    // Names that can be completed at the prompt: keywords, builtins and the
+    // current top-level bindings.
+    pub fn completion_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = lexer::KEYWORDS
+            .iter()
+            .map(|&(keyword, _)| keyword)
+            .chain(builtins::names())
+            .map(str::to_string)
+            .chain(
+                self.env
+                    .borrow()
+                    .bindings()
+                    .into_iter()
+                    .map(|(name, _)| name.to_string()),
+            )
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn run_command(&mut self, line: &str, output: &mut impl Write) -> io::Result<()> {
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        let argument = argument.trim();
+        match (command, argument) {
+            (":tokens", source) if !source.is_empty() => {
+                for token in Lexer::new(source.as_bytes()).preserve_comments(true) {
+                    writeln!(
+                        output,
+                        "{}:{} {}",
+                        token.row_pos + 1,
+                        token.col_pos + 1,
+                        token.token
+                    )?;
+                }
+                Ok(())
+            }
+            (":ast", source) if !source.is_empty() => {
+                let (program, errors) =
+                    Parser::new(Lexer::new(source.as_bytes())).parse_program_partial();
+                writeln!(output, "{program}")?;
+                self.write_diagnostics(source, errors.iter().map(Diagnostic::from), output)
+            }
+            (":env", "") => {
+                for (name, value) in self.env.borrow().bindings() {
+                    writeln!(output, "{name} = {}", value.inspect())?;
+                }
+                Ok(())
+            }
+            (":reset", "") => {
+                self.env = Environment::new();
+                Ok(())
+            }
+            (":load", path) if !path.is_empty() => match std::fs::read_to_string(path) {
+                Ok(source) => self.eval_source(&source, output),
+                Err(err) => writeln!(output, "error: cannot read {path}: {err}"),
+            },
+            (":help", "") => write!(output, "{HELP}"),
+            (":tokens" | ":ast" | ":env" | ":reset" | ":load" | ":help", _) => {
+                writeln!(
+                    output,
+                    "error: wrong usage of {command}, type :help for a list of commands"
+                )
+            }
+            _ => writeln!(
+                output,
+                "error: unknown command {command}, type :help for a list of commands"
+            ),
+        }
+    }
+
+    fn write_diagnostics(
+        &self,
+        source: &str,
+        diagnostics: impl Iterator<Item = Diagnostic>,
+        output: &mut impl Write,
+    ) -> io::Result<()> {
+        for diagnostic in diagnostics {
+            writeln!(output, "{}", diagnostic.render(source, self.color))?;
+        }
+        Ok(())
+    }
+
+    // `null` results, such as those of `let` statements, are not echoed.
+    pub fn eval_source(&mut self, source: &str, output: &mut impl Write) -> io::Result<()> {
+        match parser::parse_str(source) {
+            Ok(program) => match evaluator::eval(&program, &self.env) {
+                Ok(Object::Null) => Ok(()),
+                Ok(object) => writeln!(output, "{object}"),
+                Err(err) => {
+                    self.write_diagnostics(source, std::iter::once(Diagnostic::from(&err)), output)
+                }
+            },
+            Err(errors) => {
+                self.write_diagnostics(source, errors.iter().map(Diagnostic::from), output)
+            }
+        }
+    }
+}
+
+// Input is incomplete while brackets are left open, a string or comment is
+// unterminated, it ends with an operator that still needs an operand, or the
+// parser ran out of tokens.
+pub fn is_complete(source: &str) -> bool {
+    let mut depth = 0;
+    let mut last = Token::EOF;
+    for token in Lexer::new(source.as_bytes()) {
+        match token.token {
+            Token::LPAREN | Token::LBRACE | Token::LBRACKET => depth += 1,
+            Token::RPAREN | Token::RBRACE | Token::RBRACKET => depth -= 1,
+            Token::ILLEGAL(
+                IllegalKind::UnterminatedComment | IllegalKind::UnterminatedString,
+                _,
+            ) => return false,
+            Token::EOF => break,
+            _ => {}
+        }
+        last = token.token;
+    }
+    if depth > 0 || expects_operand(&last) {
+        return false;
+    }
+    match parser::parse_str(source) {
+        Ok(_) => true,
+        Err(errors) => !errors
+            .iter()
+            .any(|err| err.kind == ParseErrorKind::UnexpectedEof),
+    }
+}
+
+fn expects_operand(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::ASSIGN
+            | Token::PLUS
+            | Token::MINUS
+            | Token::BANG
+            | Token::ASTERISK
+            | Token::SLASH
+            | Token::LT
+            | Token::GT
+            | Token::EQ
+            | Token::NEQ
+            | Token::COMMA
+            | Token::COLON
+    )
+}
+
+// Completes the identifier that ends at `pos`. Returns where the completed
+// word starts and the matching names.
+pub fn complete(line: &str, pos: usize, names: &[String]) -> (usize, Vec<String>) {
+    let start = line[..pos]
+        .bytes()
+        .rposition(|ch| !lexer::is_letter(ch) && !lexer::is_digit(ch))
+        .map_or(0, |i| i + 1);
+    let word = &line[start..pos];
+    if word.is_empty() {
+        return (pos, Vec::new());
+    }
+    let candidates = names
+        .iter()
+        .filter(|name| name.starts_with(word))
+        .cloned()
+        .collect();
+    (start, candidates)
+}
@@ -0,0 +1,40 @@
+use crate::diagnostics::Diagnostic;
+use crate::environment::Environment;
+use crate::evaluator::{self, RuntimeError};
+use crate::object::Object;
+use crate::parser::{self, ParseError};
+
+// Exit codes follow the BSD `sysexits.h` conventions.
+pub const EXIT_USAGE: u8 = 64;
+pub const EXIT_PARSE_ERROR: u8 = 65;
+pub const EXIT_NO_INPUT: u8 = 66;
+pub const EXIT_RUNTIME_ERROR: u8 = 70;
+
+#[derive(Debug, PartialEq)]
+pub enum Failure {
+    Parse(Vec<ParseError>),
+    Runtime(RuntimeError),
+}
+
+impl Failure {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Parse(_) => EXIT_PARSE_ERROR,
+            Self::Runtime(_) => EXIT_RUNTIME_ERROR,
+        }
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        match self {
+            Self::Parse(errors) => errors.iter().map(Diagnostic::from).collect(),
+            Self::Runtime(err) => vec![Diagnostic::from(err)],
+        }
+    }
+}
+
+// Runs a whole program in a fresh environment. Nothing is evaluated if any
+// part of it fails to parse.
+pub fn run(source: &str) -> Result<Object, Failure> {
+    let program = parser::parse_str(source).map_err(Failure::Parse)?;
+    evaluator::eval(&program, &Environment::new()).map_err(Failure::Runtime)
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use monkey_interpreter::builtins;
+    use monkey_interpreter::environment::Environment;
+    use monkey_interpreter::evaluator::{eval, RuntimeErrorKind};
+    use monkey_interpreter::object::Object;
+    use monkey_interpreter::parser::parse_str;
+
+    fn run(input: &str) -> Result<Object, RuntimeErrorKind> {
+        let program = parse_str(input).expect("failed to parse program");
+        eval(&program, &Environment::new()).map_err(|err| err.kind)
+    }
+
+    fn ints(values: &[i32]) -> Object {
+        Object::Array(values.iter().map(|&n| Object::Integer(n)).collect())
+    }
+
+    #[test]
+    fn test_builtin_lookup() {
+        assert!(matches!(
+            builtins::lookup("len"),
+            Some(Object::Builtin(builtin)) if builtin.name == "len"
+        ));
+        assert_eq!(builtins::lookup("nope"), None);
+        assert_eq!(
+            run("len;").map(|o| o.to_string()),
+            Ok("builtin len".to_string())
+        );
+        assert_eq!(run("let len = 5; len;"), Ok(Object::Integer(5)));
+    }
+
+    #[test]
+    fn test_builtin_len() {
+        assert_eq!(run("len(\"\");"), Ok(Object::Integer(0)));
+        assert_eq!(run("len(\"héllo\");"), Ok(Object::Integer(5)));
+        assert_eq!(run("len([1, 2, 3]);"), Ok(Object::Integer(3)));
+        assert_eq!(run("len({1: 2});"), Ok(Object::Integer(1)));
+        assert_eq!(
+            run("len(1);"),
+            Err(RuntimeErrorKind::InvalidArgument {
+                function: "len",
+                expected: "STRING, ARRAY or HASH",
+                got: "INTEGER"
+            })
+        );
+        assert_eq!(
+            run("len(\"a\", \"b\");"),
+            Err(RuntimeErrorKind::WrongArgumentCount {
+                function: Some("len"),
+                expected: 1,
+                got: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_builtin_array_functions() {
+        assert_eq!(run("first([1, 2, 3]);"), Ok(Object::Integer(1)));
+        assert_eq!(run("last([1, 2, 3]);"), Ok(Object::Integer(3)));
+        assert_eq!(run("rest([1, 2, 3]);"), Ok(ints(&[2, 3])));
+        assert_eq!(run("first([]);"), Ok(Object::Null));
+        assert_eq!(run("last([]);"), Ok(Object::Null));
+        assert_eq!(run("rest([]);"), Ok(Object::Null));
+        assert_eq!(
+            run("let a = [1]; let b = push(a, 2); [a, b];"),
+            Ok(Object::Array(vec![ints(&[1]), ints(&[1, 2])]))
+        );
+        assert_eq!(
+            run("rest(\"abc\");"),
+            Err(RuntimeErrorKind::InvalidArgument {
+                function: "rest",
+                expected: "ARRAY",
+                got: "STRING"
+            })
+        );
+        assert_eq!(
+            run("push([]);"),
+            Err(RuntimeErrorKind::WrongArgumentCount {
+                function: Some("push"),
+                expected: 2,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_builtin_in_user_functions() {
+        let input = "
+            let map = fn(arr, f) {
+                let iter = fn(arr, acc) {
+                    if (len(arr) == 0) { acc } else { iter(rest(arr), push(acc, f(first(arr)))) }
+                };
+                iter(arr, [])
+            };
+            map([1, 2, 3], fn(x) { x * 2 });
+        ";
+        assert_eq!(run(input), Ok(ints(&[2, 4, 6])));
+        assert_eq!(run("puts(\"hi\", 1);"), Ok(Object::Null));
+    }
+
+    #[test]
+    fn test_builtin_error_span() {
+        let input = "let a = 1;\nlen(a);";
+        let program = parse_str(input).expect("failed to parse program");
+        let err = eval(&program, &Environment::new()).expect_err("expected runtime error");
+        assert_eq!(&input[err.span.start..err.span.end], "len(a)");
+        assert_eq!(
+            err.to_string(),
+            "argument to `len` must be STRING, ARRAY or HASH, got INTEGER"
+        );
+    }
+
+    #[test]
+    fn test_builtin_arity_error_message() {
+        let program = parse_str("len(\"a\", \"b\");").expect("failed to parse program");
+        let err = eval(&program, &Environment::new()).expect_err("expected runtime error");
+        assert_eq!(
+            err.to_string(),
+            "wrong number of arguments to `len`: expected 1, got 2"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use monkey_interpreter::diagnostics::Diagnostic;
+    use monkey_interpreter::environment::Environment;
+    use monkey_interpreter::evaluator::eval;
+    use monkey_interpreter::lexer::{Lexer, Span};
+    use monkey_interpreter::parser::Parser;
+
+    #[test]
+    fn test_render_parse_error() {
+        let source = "let a = 1;\nlet 5 = 1;";
+        let mut lexer = Lexer::new(source.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let errors = parser.parse_program().expect_err("expected parse error");
+        let rendered = Diagnostic::from(&errors[0]).render(source, false);
+        assert_eq!(
+            rendered,
+            "\
+error: expected IDENT, got INT(5)
+ --> 2:5
+  |
+2 | let 5 = 1;
+  |     ^ expected IDENT"
+        );
+    }
+
+    #[test]
+    fn test_render_unexpected_eof_before_trailing_lines() {
+        let source = "let a = [1,\n  2\n\n\n";
+        let mut lexer = Lexer::new(source.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let errors = parser.parse_program().expect_err("expected parse error");
+        let rendered = Diagnostic::from(&errors[0]).render(source, false);
+        assert_eq!(
+            rendered,
+            "\
+error: expected RBRACKET, got EOF
+ --> 2:4
+  |
+2 |   2
+  |    ^ expected RBRACKET"
+        );
+    }
+
+    #[test]
+    fn test_render_runtime_error() {
+        let source = "let x = 1;\n\tx + true;";
+        let mut lexer = Lexer::new(source.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse_program().expect("failed to parse program");
+        let err = eval(&program, &Environment::new()).expect_err("expected runtime error");
+        let rendered = Diagnostic::from(&err).render(source, false);
+        assert_eq!(
+            rendered,
+            "\
+error: type mismatch: INTEGER + BOOLEAN
+ --> 2:2
+  |
+2 | \tx + true;
+  | \t^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn test_render_column_after_multibyte_text() {
+        let source = "let s = \"éé\"; s + true";
+        let mut lexer = Lexer::new(source.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse_program().expect("failed to parse program");
+        let err = eval(&program, &Environment::new()).expect_err("expected runtime error");
+        let rendered = Diagnostic::from(&err).render(source, false);
+        assert_eq!(
+            rendered,
+            "\
+error: type mismatch: STRING + BOOLEAN
+ --> 1:15
+  |
+1 | let s = \"éé\"; s + true
+  |               ^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn test_render_warning_with_notes() {
+        let source = "let unused = 1;";
+        let rendered = Diagnostic::warning("unused variable", Span::new(4, 10))
+            .with_label("never read")
+            .with_note("prefix it with an underscore to silence this warning")
+            .render(source, false);
+        assert_eq!(
+            rendered,
+            "\
+warning: unused variable
+ --> 1:5
+  |
+1 | let unused = 1;
+  |     ^^^^^^ never read
+  = note: prefix it with an underscore to silence this warning"
+        );
+    }
+
+    #[test]
+    fn test_render_color() {
+        let rendered = Diagnostic::error("boom", Span::new(0, 1)).render("x", true);
+        assert!(rendered.starts_with("\x1b[1;31merror\x1b[0m"));
+        assert!(rendered.contains("\x1b[1;31m^\x1b[0m"));
+    }
+}
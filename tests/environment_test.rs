@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+    use monkey_interpreter::environment::Environment;
+    use monkey_interpreter::object::Object;
+
+    #[test]
+    fn test_environment_get_set() {
+        let env = Environment::new();
+        assert_eq!(env.borrow().get("a"), None);
+        env.borrow_mut().set("a", Object::Integer(1));
+        assert_eq!(env.borrow().get("a"), Some(Object::Integer(1)));
+        env.borrow_mut().set("a", Object::Integer(2));
+        assert_eq!(env.borrow().get("a"), Some(Object::Integer(2)));
+    }
+
+    #[test]
+    fn test_environment_enclosed_lookup() {
+        let outer = Environment::new();
+        outer.borrow_mut().set("a", Object::Integer(1));
+        let inner = Environment::new_enclosed(&outer);
+        assert_eq!(inner.borrow().get("a"), Some(Object::Integer(1)));
+
+        // Bindings made in the outer scope after the inner one was created are visible.
+        outer.borrow_mut().set("b", Object::Integer(2));
+        assert_eq!(inner.borrow().get("b"), Some(Object::Integer(2)));
+    }
+
+    #[test]
+    fn test_environment_shadowing() {
+        let outer = Environment::new();
+        outer.borrow_mut().set("a", Object::Integer(1));
+        let inner = Environment::new_enclosed(&outer);
+        inner.borrow_mut().set("a", Object::Boolean(true));
+
+        assert_eq!(inner.borrow().get("a"), Some(Object::Boolean(true)));
+        assert_eq!(outer.borrow().get("a"), Some(Object::Integer(1)));
+
+        inner.borrow_mut().set("c", Object::Null);
+        assert_eq!(outer.borrow().get("c"), None);
+    }
+}
@@ -0,0 +1,395 @@
+#[cfg(test)]
+mod tests {
+    use monkey_interpreter::ast;
+    use monkey_interpreter::environment::Environment;
+    use monkey_interpreter::evaluator::{eval, RuntimeErrorKind, MAX_EVAL_DEPTH};
+    use monkey_interpreter::lexer::Lexer;
+    use monkey_interpreter::object::Object;
+    use monkey_interpreter::parser::{parse_str, Parser};
+
+    fn run(input: &str) -> Result<Object, RuntimeErrorKind> {
+        let mut lexer = Lexer::new(input.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse_program().expect("failed to parse program");
+        eval(&program, &Environment::new()).map_err(|err| err.kind)
+    }
+
+    #[test]
+    fn test_eval_integer_arithmetic() {
+        let cases = [
+            ("5;", 5),
+            ("1 + 2;", 3),
+            ("10 - 4 - 3;", 3),
+            ("(1 + 2) * 3;", 9),
+            ("2 + 3 * 4;", 14),
+            ("20 / 3;", 6),
+        ];
+        for (input, expected) in cases.iter() {
+            assert_eq!(run(input), Ok(Object::Integer(*expected)), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_eval_let_and_variables() {
+        assert_eq!(run("let a = 5; let b = a + 1; b;"), Ok(Object::Integer(6)));
+        assert_eq!(
+            run("c;"),
+            Err(RuntimeErrorKind::UnknownIdentifier("c".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_eval_return_and_if() {
+        assert_eq!(run("return 1; 2;"), Ok(Object::Integer(1)));
+        assert_eq!(
+            run("let a = 1; if (a) { return a + 1; } else { return 0; }; 5;"),
+            Ok(Object::Integer(2))
+        );
+    }
+
+    #[test]
+    fn test_eval_integer_comparison() {
+        let cases = [
+            ("1 < 2;", true),
+            ("1 > 2;", false),
+            ("1 + 1 == 2;", true),
+            ("1 != 1;", false),
+        ];
+        for (input, expected) in cases.iter() {
+            assert_eq!(run(input), Ok(Object::Boolean(*expected)), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_eval_boolean_expressions() {
+        let cases = [
+            ("true;", true),
+            ("false;", false),
+            ("true == true;", true),
+            ("true != false;", true),
+            ("(1 < 2) == true;", true),
+            ("(1 > 2) == true;", false),
+            ("!true == false;", true),
+        ];
+        for (input, expected) in cases.iter() {
+            assert_eq!(run(input), Ok(Object::Boolean(*expected)), "{input}");
+        }
+        assert_eq!(
+            run("true + false;"),
+            Err(RuntimeErrorKind::TypeMismatch(
+                ast::BinaryOperator::Add,
+                "BOOLEAN",
+                "BOOLEAN"
+            ))
+        );
+        assert_eq!(
+            run("1 == true;"),
+            Err(RuntimeErrorKind::TypeMismatch(
+                ast::BinaryOperator::Eq,
+                "INTEGER",
+                "BOOLEAN"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_eval_strings() {
+        assert_eq!(
+            run("\"Hello\" + \", \" + \"World!\";"),
+            Ok(Object::String("Hello, World!".to_string()))
+        );
+        assert_eq!(run("\"a\" == \"a\";"), Ok(Object::Boolean(true)));
+        assert_eq!(run("\"a\" != \"a\";"), Ok(Object::Boolean(false)));
+        assert_eq!(
+            run("let greet = fn(name) { \"hi \" + name }; greet(\"bob\");"),
+            Ok(Object::String("hi bob".to_string()))
+        );
+        assert_eq!(
+            run("\"a\" - \"b\";"),
+            Err(RuntimeErrorKind::TypeMismatch(
+                ast::BinaryOperator::Subtract,
+                "STRING",
+                "STRING"
+            ))
+        );
+        assert_eq!(
+            run("\"a\" + 1;"),
+            Err(RuntimeErrorKind::TypeMismatch(
+                ast::BinaryOperator::Add,
+                "STRING",
+                "INTEGER"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_eval_if_conditions() {
+        assert_eq!(
+            run("if (1 > 2) { return 1; } else { return 2; };"),
+            Ok(Object::Integer(2))
+        );
+        assert_eq!(
+            run("let t = true; if (!t) { 1; } else { 2; };"),
+            Ok(Object::Integer(2))
+        );
+    }
+
+    #[test]
+    fn test_eval_if_expressions() {
+        assert_eq!(
+            run("let x = if (1 < 2) { 10 } else { 20 }; x;"),
+            Ok(Object::Integer(10))
+        );
+        assert_eq!(run("if (false) { 10 }"), Ok(Object::Null));
+        let input = "
+            let sign = fn(n) {
+                if (n < 0) { -1 } else if (n == 0) { 0 } else { 1 }
+            };
+            sign(-5) * 100 + sign(0) * 10 + sign(7);
+        ";
+        assert_eq!(run(input), Ok(Object::Integer(-99)));
+        assert_eq!(
+            run("let f = fn() { let x = if (true) { return 1; }; 2; }; f();"),
+            Ok(Object::Integer(1))
+        );
+    }
+
+    #[test]
+    fn test_eval_block_scope() {
+        assert_eq!(
+            run("let x = 1; if (true) { let a = 1; a + 2; } else { 0; };"),
+            Ok(Object::Integer(3))
+        );
+        assert_eq!(
+            run("let x = 1; if (true) { let x = 2; } else { }; x;"),
+            Ok(Object::Integer(1))
+        );
+        assert_eq!(
+            run("if (true) { let a = 1; } else { }; a;"),
+            Err(RuntimeErrorKind::UnknownIdentifier("a".to_string()))
+        );
+        assert_eq!(
+            run("let f = fn() { if (true) { return 1; } else { }; 2; }; f();"),
+            Ok(Object::Integer(1))
+        );
+    }
+
+    #[test]
+    fn test_eval_prefix_operators() {
+        assert_eq!(run("-5;"), Ok(Object::Integer(-5)));
+        assert_eq!(run("--5;"), Ok(Object::Integer(5)));
+        assert_eq!(run("10 - -5;"), Ok(Object::Integer(15)));
+        assert_eq!(run("!true;"), Ok(Object::Boolean(false)));
+        assert_eq!(run("!!true;"), Ok(Object::Boolean(true)));
+        assert_eq!(run("!0;"), Ok(Object::Boolean(false)));
+        assert_eq!(run("let f = fn() { }; !f();"), Ok(Object::Boolean(true)));
+        assert_eq!(
+            run("-true;"),
+            Err(RuntimeErrorKind::InvalidOperand(
+                ast::PrefixOperator::Negate,
+                "BOOLEAN"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_eval_division_by_zero() {
+        assert_eq!(run("1 / 0;"), Err(RuntimeErrorKind::DivisionByZero));
+    }
+
+    #[test]
+    fn test_eval_integer_overflow() {
+        assert!(matches!(
+            run("2147483647 + 1;"),
+            Err(RuntimeErrorKind::IntegerOverflow(..))
+        ));
+    }
+
+    #[test]
+    fn test_eval_function_call() {
+        assert_eq!(
+            run("let add = fn(x, y) { x + y; }; add(1, 2);"),
+            Ok(Object::Integer(3))
+        );
+        assert_eq!(
+            run("let f = fn() { return 1; 2; }; f() + 1;"),
+            Ok(Object::Integer(2))
+        );
+        assert_eq!(run("fn(x) { x; }(7);"), Ok(Object::Integer(7)));
+    }
+
+    #[test]
+    fn test_eval_closures() {
+        let input = "
+            let adder = fn(x) { fn(y) { x + y; }; };
+            let add_two = adder(2);
+            let apply = fn(f, v) { f(v); };
+            apply(add_two, 3) + adder(10)(20);
+        ";
+        assert_eq!(run(input), Ok(Object::Integer(35)));
+    }
+
+    #[test]
+    fn test_eval_call_errors() {
+        assert_eq!(
+            run("let f = fn(x) { x; }; f(1, 2);"),
+            Err(RuntimeErrorKind::WrongArgumentCount {
+                function: None,
+                expected: 1,
+                got: 2
+            })
+        );
+        assert_eq!(run("5(1);"), Err(RuntimeErrorKind::NotAFunction("INTEGER")));
+    }
+
+    #[test]
+    fn test_eval_arrays() {
+        assert_eq!(
+            run("[1, 2 * 2, \"three\"];"),
+            Ok(Object::Array(vec![
+                Object::Integer(1),
+                Object::Integer(4),
+                Object::String("three".to_string())
+            ]))
+        );
+        assert_eq!(run("[];"), Ok(Object::Array(vec![])));
+        assert_eq!(
+            run("let a = [1, 2, 3]; a[0] + a[2];"),
+            Ok(Object::Integer(4))
+        );
+        assert_eq!(run("[1, 2, 3][1 + 1];"), Ok(Object::Integer(3)));
+        assert_eq!(run("[[1, 2], [3]][0][1];"), Ok(Object::Integer(2)));
+        assert_eq!(
+            run("let first = fn(a) { a[0] }; first([5, 6]);"),
+            Ok(Object::Integer(5))
+        );
+    }
+
+    #[test]
+    fn test_eval_array_indexing() {
+        assert_eq!(run("[1, 2, 3][-1];"), Ok(Object::Integer(3)));
+        assert_eq!(run("[1, 2, 3][-3];"), Ok(Object::Integer(1)));
+        assert_eq!(
+            run("[1, 2, 3][3];"),
+            Err(RuntimeErrorKind::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert_eq!(
+            run("[1, 2, 3][-4];"),
+            Err(RuntimeErrorKind::IndexOutOfBounds { index: -4, len: 3 })
+        );
+        assert_eq!(
+            run("[][0];"),
+            Err(RuntimeErrorKind::IndexOutOfBounds { index: 0, len: 0 })
+        );
+        assert_eq!(
+            run("[1][true];"),
+            Err(RuntimeErrorKind::InvalidIndex("ARRAY", "BOOLEAN"))
+        );
+        assert_eq!(run("1[0];"), Err(RuntimeErrorKind::NotIndexable("INTEGER")));
+    }
+
+    #[test]
+    fn test_eval_hashes() {
+        assert_eq!(
+            run("let key = \"b\"; let h = {\"a\": 1, key: 2, 3: true, false: \"no\"}; [h[\"a\"], h[\"b\"], h[1 + 2], h[false]];"),
+            Ok(Object::Array(vec![
+                Object::Integer(1),
+                Object::Integer(2),
+                Object::Boolean(true),
+                Object::String("no".to_string())
+            ]))
+        );
+        assert_eq!(run("{1: 1, 1: 2}[1];"), Ok(Object::Integer(2)));
+        assert_eq!(run("{\"a\": 1}[\"b\"];"), Ok(Object::Null));
+        assert_eq!(run("{}[0];"), Ok(Object::Null));
+        assert_eq!(
+            run("{[1]: 2};"),
+            Err(RuntimeErrorKind::UnhashableKey("ARRAY"))
+        );
+        assert_eq!(
+            run("{1: 2}[fn() { 1 }];"),
+            Err(RuntimeErrorKind::UnhashableKey("FUNCTION"))
+        );
+    }
+
+    #[test]
+    fn test_eval_return_from_operands() {
+        let cases = [
+            (
+                "let f = fn() { let a = [if (true) { return 1; }]; 2 }; f();",
+                1,
+            ),
+            (
+                "let f = fn() { len([0, if (true) { return 1; }]); 2 }; f();",
+                1,
+            ),
+            ("let f = fn() { 1 + if (true) { return 2; } }; f();", 2),
+            ("let f = fn() { -if (true) { return 3; } }; f();", 3),
+            (
+                "let f = fn() { if (if (true) { return 4; }) { 5 } else { 6 } }; f();",
+                4,
+            ),
+            ("let f = fn() { {1: if (true) { return 7; }} }; f();", 7),
+            ("let f = fn() { [1][if (true) { return 8; }] }; f();", 8),
+            ("1 + if (true) { return 9; };", 9),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(run(input), Ok(Object::Integer(expected)), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_eval_depth_limit() {
+        // Test threads get 2 MiB of stack, so run with what a main thread gets.
+        let results = std::thread::Builder::new()
+            .stack_size(8 * 1024 * 1024)
+            .spawn(|| {
+                let env = Environment::new();
+                let eval_str = |input: &str| {
+                    let program = parse_str(input).expect("failed to parse program");
+                    eval(&program, &env).map_err(|err| err.kind)
+                };
+                let nested = |depth: usize| {
+                    format!("{}1{};", "[".repeat(depth - 1), "]".repeat(depth - 1))
+                };
+                eval_str("let f = fn(n) { if (n < 1) { 0 } else { 1 + f(n - 1) } };").unwrap();
+                eval_str(
+                    "let g = fn(n) { if (n < 1) { 0 } else { first([if (true) { -(-g(n - 1)) }]) } };",
+                )
+                .unwrap();
+                [
+                    eval_str(&nested(MAX_EVAL_DEPTH)).map(|_| "nested".to_string()),
+                    eval_str(&nested(MAX_EVAL_DEPTH + 1)).map(|_| "nested".to_string()),
+                    eval_str("f(100);").map(|object| object.to_string()),
+                    eval_str("f(1000);").map(|object| object.to_string()),
+                    eval_str("g(1000);").map(|object| object.to_string()),
+                    // The depth is restored after the error.
+                    eval_str("f(10);").map(|object| object.to_string()),
+                ]
+            })
+            .expect("failed to spawn thread")
+            .join()
+            .expect("evaluation panicked");
+        assert_eq!(
+            results,
+            [
+                Ok("nested".to_string()),
+                Err(RuntimeErrorKind::StackOverflow),
+                Ok("100".to_string()),
+                Err(RuntimeErrorKind::StackOverflow),
+                Err(RuntimeErrorKind::StackOverflow),
+                Ok("10".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eval_error_spans() {
+        let input = "let f = fn(x) { x + true };\nf(1);";
+        let mut lexer = Lexer::new(input.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse_program().expect("failed to parse program");
+        let err = eval(&program, &Environment::new()).expect_err("expected runtime error");
+        assert_eq!(&input[err.span.start..err.span.end], "x + true");
+    }
+}
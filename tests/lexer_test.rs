@@ -99,7 +99,7 @@ mod tests {
     #[test]
     fn test_lexer_additional() {
         let input_string = "
-            !-/*5;
+            !-/ *5;
             5 < 10 > 5;
         ";
         let mut lexer = Lexer::new(input_string.as_bytes());
@@ -197,4 +197,260 @@ mod tests {
             assert_eq!(lexer.next_token().token, *token)
         }
     }
+
+    #[test]
+    fn test_lexer_brackets() {
+        let lexer = Lexer::new("a[1, 2]".as_bytes());
+
+        let tokens: Vec<Token> = lexer.map(|t| t.token).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::IDENT("a".to_string()),
+                Token::LBRACKET,
+                Token::INT(1),
+                Token::COMMA,
+                Token::INT(2),
+                Token::RBRACKET,
+                Token::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_hash() {
+        let lexer = Lexer::new(r#"{"a": 1}"#.as_bytes());
+
+        let tokens: Vec<Token> = lexer.map(|t| t.token).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LBRACE,
+                Token::STRING("a".to_string()),
+                Token::COLON,
+                Token::INT(1),
+                Token::RBRACE,
+                Token::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_positions() {
+        let input_string = "let x = 10;\n  x + y;";
+        let mut lexer = Lexer::new(input_string.as_bytes());
+
+        let positions_truth = [
+            (Token::LET, Span::new(0, 3), 0, 0),
+            (Token::IDENT("x".to_string()), Span::new(4, 5), 0, 4),
+            (Token::ASSIGN, Span::new(6, 7), 0, 6),
+            (Token::INT(10), Span::new(8, 10), 0, 8),
+            (Token::SEMICOLON, Span::new(10, 11), 0, 10),
+            (Token::IDENT("x".to_string()), Span::new(14, 15), 1, 2),
+            (Token::PLUS, Span::new(16, 17), 1, 4),
+            (Token::IDENT("y".to_string()), Span::new(18, 19), 1, 6),
+            (Token::SEMICOLON, Span::new(19, 20), 1, 7),
+            (Token::EOF, Span::new(20, 20), 1, 8),
+        ];
+        for (token, span, row_pos, col_pos) in positions_truth.iter() {
+            let token_with_meta = lexer.next_token();
+            assert_eq!(token_with_meta.token, *token);
+            assert_eq!(token_with_meta.span, *span);
+            assert_eq!(token_with_meta.row_pos, *row_pos);
+            assert_eq!(token_with_meta.col_pos, *col_pos);
+        }
+    }
+
+    #[test]
+    fn test_span_positions() {
+        let input = "let a = 1;\nlet bc = a;".as_bytes();
+        let span = Span::new(15, 17);
+        assert_eq!(span.start_position(input), Position { row: 1, col: 4 });
+        assert_eq!(span.end_position(input), Position { row: 1, col: 6 });
+        assert_eq!(Span::new(4, 5).to(Span::new(8, 9)), Span::new(4, 9));
+    }
+
+    #[test]
+    fn test_lexer_iterator() {
+        let lexer = Lexer::new("let x = 1;".as_bytes());
+        let tokens: Vec<Token> = lexer.map(|t| t.token).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LET,
+                Token::IDENT("x".to_string()),
+                Token::ASSIGN,
+                Token::INT(1),
+                Token::SEMICOLON,
+                Token::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_comments() {
+        let input_string = "
+            // leading comment
+            let a = 10 / 2; // trailing comment
+            /* block /* nested */ still comment */ a
+        ";
+        let lexer = Lexer::new(input_string.as_bytes());
+
+        let tokens: Vec<Token> = lexer.map(|t| t.token).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LET,
+                Token::IDENT("a".to_string()),
+                Token::ASSIGN,
+                Token::INT(10),
+                Token::SLASH,
+                Token::INT(2),
+                Token::SEMICOLON,
+                Token::IDENT("a".to_string()),
+                Token::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_preserve_comments() {
+        let input_string = "a // one\n/* two */";
+        let lexer = Lexer::new(input_string.as_bytes()).preserve_comments(true);
+
+        let tokens: Vec<(Token, Span)> = lexer.map(|t| (t.token, t.span)).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (Token::IDENT("a".to_string()), Span::new(0, 1)),
+                (Token::COMMENT("// one".to_string()), Span::new(2, 8)),
+                (Token::COMMENT("/* two */".to_string()), Span::new(9, 18)),
+                (Token::EOF, Span::new(18, 18)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_unterminated_block_comment() {
+        let input_string = "a /* one /* two */";
+        let mut lexer = Lexer::new(input_string.as_bytes());
+
+        assert_eq!(lexer.next_token().token, Token::IDENT("a".to_string()));
+        let illegal = lexer.next_token();
+        assert_eq!(
+            illegal.token,
+            Token::ILLEGAL(
+                IllegalKind::UnterminatedComment,
+                "/* one /* two */".to_string()
+            )
+        );
+        assert_eq!(illegal.span, Span::new(2, 18));
+        assert_eq!(lexer.next_token().token, Token::EOF);
+    }
+
+    #[test]
+    fn test_lexer_strings() {
+        let input_string = r#""hello" "a\tb\n" "say \"hi\" \\ \u{1F600}" "héllo""#;
+        let lexer = Lexer::new(input_string.as_bytes());
+
+        let tokens: Vec<Token> = lexer.map(|t| t.token).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::STRING("hello".to_string()),
+                Token::STRING("a\tb\n".to_string()),
+                Token::STRING("say \"hi\" \\ \u{1F600}".to_string()),
+                Token::STRING("héllo".to_string()),
+                Token::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_invalid_strings() {
+        let input_string = r#"let a = "bad \q escape" + "\u{zz}"; "open"#;
+        let lexer = Lexer::new(input_string.as_bytes());
+
+        let illegal: Vec<(Token, Span)> = lexer
+            .filter(|t| matches!(t.token, Token::ILLEGAL(..)))
+            .map(|t| (t.token, t.span))
+            .collect();
+        assert_eq!(
+            illegal,
+            vec![
+                (
+                    Token::ILLEGAL(IllegalKind::InvalidEscape, r#""bad \q escape""#.to_string()),
+                    Span::new(8, 23)
+                ),
+                (
+                    Token::ILLEGAL(IllegalKind::InvalidEscape, r#""\u{zz}""#.to_string()),
+                    Span::new(26, 34)
+                ),
+                (
+                    Token::ILLEGAL(IllegalKind::UnterminatedString, r#""open"#.to_string()),
+                    Span::new(36, 41)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lookup_keyword() {
+        assert_eq!(lookup_keyword("fn"), Some(Token::FUNCTION));
+        assert_eq!(lookup_keyword("return"), Some(Token::RETURN));
+        assert_eq!(lookup_keyword("returns"), None);
+        for (keyword, token) in KEYWORDS.iter() {
+            let mut lexer = Lexer::new(keyword.as_bytes());
+            assert_eq!(lexer.next_token().token, *token);
+        }
+    }
+
+    #[test]
+    fn test_lexer_shebang() {
+        let lexer = Lexer::new("#!/usr/bin/env monkey\nlet".as_bytes());
+        let tokens: Vec<Token> = lexer.map(|t| t.token).collect();
+        assert_eq!(tokens, vec![Token::LET, Token::EOF]);
+
+        // Only the very first line may be a shebang.
+        let mut lexer = Lexer::new("\n#!x".as_bytes());
+        assert_eq!(
+            lexer.next_token().token,
+            Token::ILLEGAL(IllegalKind::Character, "#".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lexer_eof_after_last_token() {
+        let mut lexer = Lexer::new("let a\n  // note\n\n\n".as_bytes());
+        let eof = lexer.find(|t| t.token == Token::EOF).unwrap();
+        assert_eq!(eof.span, Span::new(15, 15));
+        assert_eq!((eof.row_pos, eof.col_pos), (1, 9));
+
+        let mut lexer = Lexer::new("  \n ".as_bytes());
+        let eof = lexer.next_token();
+        assert_eq!(eof.token, Token::EOF);
+        assert_eq!(eof.span, Span::new(0, 0));
+        assert_eq!((eof.row_pos, eof.col_pos), (0, 0));
+    }
+
+    #[test]
+    fn test_lexer_non_ascii_character() {
+        let lexer = Lexer::new("let x = é;".as_bytes());
+        let tokens: Vec<(Token, Span)> = lexer.map(|t| (t.token, t.span)).collect();
+        assert_eq!(
+            tokens[3],
+            (
+                Token::ILLEGAL(IllegalKind::Character, "é".to_string()),
+                Span::new(8, 10)
+            )
+        );
+        assert_eq!(tokens[4], (Token::SEMICOLON, Span::new(10, 11)));
+
+        let mut lexer = Lexer::new(&[0xff, b';']);
+        assert_eq!(
+            lexer.next_token().token,
+            Token::ILLEGAL(IllegalKind::Character, "\\xFF".to_string())
+        );
+        assert_eq!(lexer.next_token().token, Token::SEMICOLON);
+    }
 }
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use monkey_interpreter::object::{HashKey, Object};
+
+    #[test]
+    fn test_object_display() {
+        assert_eq!(Object::Integer(-5).to_string(), "-5");
+        assert_eq!(Object::Boolean(true).to_string(), "true");
+        assert_eq!(Object::String("a\"b".to_string()).to_string(), "a\"b");
+        assert_eq!(
+            Object::Array(vec![
+                Object::Integer(1),
+                Object::String("a, b".to_string()),
+                Object::Array(vec![])
+            ])
+            .to_string(),
+            "[1, \"a, b\", []]"
+        );
+        assert_eq!(
+            Object::Hash(
+                [
+                    (
+                        HashKey::String("b".to_string()),
+                        Object::String("x".to_string())
+                    ),
+                    (HashKey::Integer(2), Object::Boolean(false)),
+                    (HashKey::Boolean(true), Object::Integer(1)),
+                ]
+                .into_iter()
+                .collect()
+            )
+            .to_string(),
+            "{2: false, true: 1, \"b\": \"x\"}"
+        );
+        assert_eq!(Object::Null.to_string(), "null");
+    }
+
+    #[test]
+    fn test_object_type_name() {
+        assert_eq!(Object::Integer(1).type_name(), "INTEGER");
+        assert_eq!(Object::Boolean(false).type_name(), "BOOLEAN");
+        assert_eq!(Object::String(String::new()).type_name(), "STRING");
+        assert_eq!(Object::Array(vec![]).type_name(), "ARRAY");
+        assert_eq!(Object::Hash(Default::default()).type_name(), "HASH");
+        assert_eq!(Object::Null.type_name(), "NULL");
+    }
+
+    #[test]
+    fn test_object_equality() {
+        assert_eq!(Object::Integer(1), Object::Integer(1));
+        assert_ne!(Object::Integer(1), Object::Boolean(true));
+        assert_ne!(Object::Null, Object::Boolean(false));
+    }
+}
@@ -0,0 +1,387 @@
+#[cfg(test)]
+mod tests {
+    use monkey_interpreter::ast;
+    use monkey_interpreter::lexer::Lexer;
+    use monkey_interpreter::lexer::{IllegalKind, Span, Token};
+    use monkey_interpreter::parser::{parse_str, Expected, ParseError, ParseErrorKind, Parser};
+
+    fn parse(input: &str) -> String {
+        let mut lexer = Lexer::new(input.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse_program().expect("failed to parse program");
+        program
+            .statements
+            .iter()
+            .map(|stmt| stmt.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn parse_errors(input: &str) -> Vec<ParseError> {
+        let mut lexer = Lexer::new(input.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        parser
+            .parse_program()
+            .expect_err("expected program to fail parsing")
+    }
+
+    #[test]
+    fn test_parser_left_associativity() {
+        assert_eq!(parse("1 - 2 - 3;"), "e{((INT(1) - INT(2)) - INT(3))}");
+        assert_eq!(parse("a / b * c;"), "e{((VAR(a) / VAR(b)) * VAR(c))}");
+    }
+
+    #[test]
+    fn test_parser_precedence() {
+        let cases = [
+            ("1 + 2 * 3;", "e{(INT(1) + (INT(2) * INT(3)))}"),
+            ("1 * 2 + 3;", "e{((INT(1) * INT(2)) + INT(3))}"),
+            (
+                "1 + 2 < 4 == 3 > 2;",
+                "e{(((INT(1) + INT(2)) < INT(4)) == (INT(3) > INT(2)))}",
+            ),
+            (
+                "a != b * (c + d);",
+                "e{(VAR(a) != (VAR(b) * ((VAR(c) + VAR(d)))))}",
+            ),
+            ("-a * b;", "e{((-VAR(a)) * VAR(b))}"),
+            ("!-a;", "e{(!(-VAR(a)))}"),
+            ("1 - -2;", "e{(INT(1) - (-INT(2)))}"),
+            ("-f(x);", "e{(-VAR(f)(VAR(x)))}"),
+            ("true == 1 < 2;", "e{(BOOL(true) == (INT(1) < INT(2)))}"),
+            ("!false != true;", "e{((!BOOL(false)) != BOOL(true))}"),
+            (
+                "\"a\" + \"b\" == s;",
+                "e{((STR(\"a\") + STR(\"b\")) == VAR(s))}",
+            ),
+            ("[1, 2 * 3, ];", "e{[INT(1), (INT(2) * INT(3))]}"),
+            (
+                "a * [1, 2][b + 1] * f(c)[0];",
+                "e{((VAR(a) * ([INT(1), INT(2)][(VAR(b) + INT(1))])) * (VAR(f)(VAR(c))[INT(0)]))}",
+            ),
+            (
+                "{\"a\": 1 + 2, b: [c], };",
+                "e{{STR(\"a\"): (INT(1) + INT(2)), VAR(b): [VAR(c)]}}",
+            ),
+            ("{}[k];", "e{({}[VAR(k)])}"),
+            ("-a[0];", "e{(-(VAR(a)[INT(0)]))}"),
+            ("a + f(b) * 2;", "e{(VAR(a) + (VAR(f)(VAR(b)) * INT(2)))}"),
+            (
+                "f(1 + 2, g(x))(y);",
+                "e{VAR(f)((INT(1) + INT(2)), VAR(g)(VAR(x)))(VAR(y))}",
+            ),
+        ];
+        for (input, expected) in cases.iter() {
+            assert_eq!(parse(input), *expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parser_function_literal() {
+        assert_eq!(
+            parse("let add = fn(x, y) { x + y; };"),
+            "let IDENT(add) = fn(IDENT(x), IDENT(y)) {\ne{(VAR(x) + VAR(y))}\n}"
+        );
+    }
+
+    #[test]
+    fn test_parser_block_statement() {
+        assert_eq!(
+            parse("if (x) { let a = 1; a + 2; } else { };"),
+            "e{if VAR(x) {\nlet IDENT(a) = INT(1)\ne{(VAR(a) + INT(2))}\n} else {\n\n}}"
+        );
+    }
+
+    #[test]
+    fn test_parser_if_expression() {
+        assert_eq!(
+            parse("let x = if (a) { 1 } else { 2 };"),
+            "let IDENT(x) = if VAR(a) {\ne{INT(1)}\n} else {\ne{INT(2)}\n}"
+        );
+        assert_eq!(
+            parse("if (a) { 1 } x"),
+            "e{if VAR(a) {\ne{INT(1)}\n}}\ne{VAR(x)}"
+        );
+        assert_eq!(
+            parse("if (a) { 1 } else if (b) { 2 } else { 3 }"),
+            "e{if VAR(a) {\ne{INT(1)}\n} else {\ne{if VAR(b) {\ne{INT(2)}\n} else {\ne{INT(3)}\n}}\n}}"
+        );
+    }
+
+    #[test]
+    fn test_parser_error_unexpected_token() {
+        let errors = parse_errors("let 5 = 1;");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedToken);
+        assert_eq!(errors[0].expected, vec![Expected::Identifier]);
+        assert_eq!(errors[0].found.token, Token::INT(5));
+        assert_eq!(errors[0].span, Span { start: 4, end: 5 });
+    }
+
+    #[test]
+    fn test_parser_error_unexpected_eof() {
+        let errors = parse_errors("let f = fn(x) { x");
+        assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedEof);
+        assert_eq!(errors[0].expected, vec![Expected::Token(Token::RBRACE)]);
+        assert_eq!(errors[0].span, Span { start: 17, end: 17 });
+    }
+
+    #[test]
+    fn test_parser_error_invalid_literal() {
+        let errors = parse_errors("let x = 99999999999;");
+        assert_eq!(errors[0].kind, ParseErrorKind::InvalidLiteral);
+        assert_eq!(
+            errors[0].found.token,
+            Token::ILLEGAL(IllegalKind::IntegerOverflow, "99999999999".to_string())
+        );
+        assert_eq!(errors[0].span, Span { start: 8, end: 19 });
+    }
+
+    #[test]
+    fn test_parser_error_non_ascii_character() {
+        let errors = parse_errors("let x = é;");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].found.token,
+            Token::ILLEGAL(IllegalKind::Character, "é".to_string())
+        );
+        assert_eq!(errors[0].span, Span { start: 8, end: 10 });
+        assert_eq!(
+            errors[0].to_string(),
+            "[0:8] expected expression, got ILLEGAL(é)"
+        );
+    }
+
+    fn error_positions(input: &str) -> Vec<(Token, usize)> {
+        parse_errors(input)
+            .into_iter()
+            .map(|err| (err.found.token, err.span.start))
+            .collect()
+    }
+
+    #[test]
+    fn test_parser_recovery_reports_independent_errors() {
+        let input = "let 1 = 2; let x = 3; let = 4; x + ;";
+        assert_eq!(
+            error_positions(input),
+            vec![
+                (Token::INT(1), 4),
+                (Token::ASSIGN, 26),
+                (Token::SEMICOLON, 35)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parser_recovery_inside_braces() {
+        let input = "
+            if (x) {
+                let 5 = 1;
+                let a = ;
+            } else {
+                a
+            };
+            let b = * 2;
+        ";
+        let errors = error_positions(input);
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].0, Token::INT(5));
+        assert_eq!(errors[1].0, Token::SEMICOLON);
+        assert_eq!(errors[2].0, Token::ASTERISK);
+    }
+
+    #[test]
+    fn test_parser_recovery_at_statement_keywords() {
+        let input = "let a = 1 let b = 2; if (a) { return a } fn(x) { x }; let c = 3";
+        assert_eq!(error_positions(input), vec![(Token::LET, 10)]);
+
+        let input = "let f = fn(x) { x + }; let g = fn(y) { y } }; let h = 1;";
+        assert_eq!(
+            error_positions(input),
+            vec![(Token::RBRACE, 20), (Token::RBRACE, 43)]
+        );
+    }
+
+    #[test]
+    fn test_parser_partial_program() {
+        let input = "let a = 1; let b = ; )) 2; let c = fn(x) { let = 1; x }; c(a)";
+        let mut lexer = Lexer::new(input.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let (program, errors) = parser.parse_program_partial();
+
+        assert_eq!(errors.len(), 3);
+        let rendered: Vec<String> = program
+            .statements
+            .iter()
+            .map(|stmt| stmt.to_string())
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "let IDENT(a) = INT(1)",
+                "let IDENT(b) = ERROR",
+                "ERROR",
+                "let IDENT(c) = fn(IDENT(x)) {\nERROR\ne{VAR(x)}\n}",
+                "e{VAR(c)(VAR(a))}",
+            ]
+        );
+
+        let ast::StatementKind::Let(_, value) = &program.statements[1].kind else {
+            panic!("expected let statement");
+        };
+        assert_eq!(value.span, Span::new(19, 19));
+        assert_eq!(program.statements[2].span, Span::new(21, 25));
+        assert_eq!(program.statements[1].span, Span::new(11, 19));
+    }
+
+    #[test]
+    fn test_parser_partial_missing_semicolon_keeps_statement() {
+        let mut lexer = Lexer::new("let a = 1 let b = 2;".as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let (program, errors) = parser.parse_program_partial();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(program.statements.len(), 2);
+        assert_eq!(program.statements[0].to_string(), "let IDENT(a) = INT(1)");
+    }
+
+    #[test]
+    fn test_parser_token_sources() {
+        let program = parse_str("let a = 1 + 2;").expect("failed to parse program");
+        assert_eq!(
+            program.statements[0].to_string(),
+            "let IDENT(a) = (INT(1) + INT(2))"
+        );
+
+        // A pre-lexed token vector without a trailing EOF token.
+        let tokens: Vec<_> = Lexer::new("f(x) * 2".as_bytes())
+            .filter(|t| t.token != Token::EOF)
+            .collect();
+        let program = Parser::new(tokens.into_iter())
+            .parse_program()
+            .expect("failed to parse program");
+        assert_eq!(
+            program.statements[0].to_string(),
+            "e{(VAR(f)(VAR(x)) * INT(2))}"
+        );
+
+        let errors = Parser::new(std::iter::empty()).parse_program_partial().1;
+        assert!(errors.is_empty());
+
+        let errors = parse_str("let a =").expect_err("expected parse error");
+        assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedEof);
+        assert_eq!(errors[0].span, Span::new(7, 7));
+
+        // A synthesized EOF after a multi-line token keeps an exact span and
+        // reports the position of that token.
+        let tokens: Vec<_> = Lexer::new("let a = f(\"x\ny\"".as_bytes())
+            .filter(|t| t.token != Token::EOF)
+            .collect();
+        let errors = Parser::new(tokens.into_iter())
+            .parse_program()
+            .expect_err("expected parse error");
+        assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedEof);
+        assert_eq!(errors[0].span, Span::new(15, 15));
+        assert_eq!((errors[0].found.row_pos, errors[0].found.col_pos), (0, 10));
+    }
+
+    #[test]
+    fn test_parser_comments() {
+        let tokens =
+            Lexer::new("let a = 1; // one\n/* two */ a".as_bytes()).preserve_comments(true);
+        let program = Parser::new(tokens)
+            .parse_program()
+            .expect("failed to parse program");
+        assert_eq!(program.statements.len(), 2);
+
+        let errors = parse_str("let a = 1; /* open").expect_err("expected parse error");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ParseErrorKind::UnterminatedComment);
+        assert_eq!(errors[0].span, Span::new(11, 18));
+    }
+
+    #[test]
+    fn test_parser_string_errors() {
+        let errors = parse_str("let s = \"abc;").expect_err("expected parse error");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ParseErrorKind::UnterminatedString);
+        assert_eq!(errors[0].span, Span::new(8, 13));
+
+        let errors = parse_str("let s = \"a\\qb\";").expect_err("expected parse error");
+        assert_eq!(errors[0].kind, ParseErrorKind::InvalidLiteral);
+        assert_eq!(errors[0].span, Span::new(8, 14));
+    }
+
+    #[test]
+    fn test_parser_array_errors() {
+        assert_eq!(
+            parse_errors("[1, 2;")[0].expected,
+            vec![Expected::Token(Token::RBRACKET)]
+        );
+        assert_eq!(
+            parse_errors("a[1;")[0].expected,
+            vec![Expected::Token(Token::RBRACKET)]
+        );
+        assert_eq!(parse_errors("a[];")[0].expected, vec![Expected::Expression]);
+    }
+
+    #[test]
+    fn test_parser_hash_literal_vs_block() {
+        assert_eq!(
+            parse("if (x) { {1: 2} } else { {} }"),
+            "e{if VAR(x) {\ne{{INT(1): INT(2)}}\n} else {\ne{{}}\n}}"
+        );
+        assert_eq!(
+            parse("let f = fn() { {\"k\": 1} }; f()[\"k\"];"),
+            "let IDENT(f) = fn() {\ne{{STR(\"k\"): INT(1)}}\n}\ne{(VAR(f)()[STR(\"k\")])}"
+        );
+    }
+
+    #[test]
+    fn test_parser_hash_errors() {
+        assert_eq!(
+            parse_errors("{1 2};")[0].expected,
+            vec![Expected::Token(Token::COLON)]
+        );
+        assert_eq!(
+            parse_errors("{1: 2 3};")[0].expected,
+            vec![Expected::Token(Token::RBRACE)]
+        );
+        // The error is reported once and parsing resumes after the hash.
+        let errors = parse_errors("let h = {1: };\nlet x = 1 +;");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedToken);
+        assert_eq!(errors[1].found.row_pos, 1);
+    }
+
+    #[test]
+    fn test_parser_spans() {
+        let input = "let add = fn(x, y) { x + y };\nadd(1, 2 * 3);";
+        let mut lexer = Lexer::new(input.as_bytes());
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse_program().expect("failed to parse program");
+
+        let let_stmt = &program.statements[0];
+        assert_eq!(let_stmt.span, Span::new(0, 28));
+        let ast::StatementKind::Let(ident, function) = &let_stmt.kind else {
+            panic!("expected let statement");
+        };
+        assert_eq!(ident.span, Span::new(4, 7));
+        assert_eq!(function.span, Span::new(10, 28));
+        let ast::ExpressionKind::Function { params, body } = &function.kind else {
+            panic!("expected function literal");
+        };
+        assert_eq!(params[1].span, Span::new(16, 17));
+        assert_eq!(body.span, Span::new(19, 28));
+        assert_eq!(body.statements[0].span, Span::new(21, 26));
+
+        let call_stmt = &program.statements[1];
+        assert_eq!(call_stmt.span, Span::new(30, 43));
+        let ast::StatementKind::Expression(call) = &call_stmt.kind else {
+            panic!("expected expression statement");
+        };
+        let ast::ExpressionKind::Call { args, .. } = &call.kind else {
+            panic!("expected call expression");
+        };
+        assert_eq!(args[1].span, Span::new(37, 42));
+    }
+}
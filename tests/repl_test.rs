@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod tests {
+    use monkey_interpreter::repl::{complete, is_complete, Repl, CONTINUATION_PROMPT, PROMPT};
+
+    // Feeds `input` line by line the way the line editor does, echoing the
+    // prompts so that the transcript reads like a terminal session.
+    fn session(input: &str) -> String {
+        let mut repl = Repl::new(false);
+        let mut output = Vec::new();
+        for line in input.split_inclusive('\n') {
+            output.extend_from_slice(repl.prompt().as_bytes());
+            repl.feed_line(line, &mut output)
+                .expect("failed to feed line");
+        }
+        output.extend_from_slice(format!("{}\n", repl.prompt()).as_bytes());
+        repl.finish(&mut output).expect("failed to finish repl");
+        String::from_utf8(output).expect("output is not utf-8")
+    }
+
+    #[test]
+    fn test_repl_keeps_environment() {
+        let output = session("let x = 2;\nlet double = fn(n) { n * 2 };\ndouble(x) + 1\n");
+        assert_eq!(output, format!("{PROMPT}{PROMPT}{PROMPT}5\n{PROMPT}\n"));
+    }
+
+    #[test]
+    fn test_repl_exits_on_eof() {
+        assert_eq!(session(""), format!("{PROMPT}\n"));
+        // A last line without a newline is still evaluated.
+        assert_eq!(session("1 + 1"), format!("{PROMPT}2\n{PROMPT}\n"));
+    }
+
+    #[test]
+    fn test_repl_prints_diagnostics() {
+        let output = session("let = 1;\nx;\n1\n");
+        assert_eq!(
+            output,
+            [
+                ">> error: expected IDENT, got ASSIGN",
+                " --> 1:5",
+                "  |",
+                "1 | let = 1;",
+                "  |     ^ expected IDENT",
+                ">> error: identifier not found: x",
+                " --> 1:1",
+                "  |",
+                "1 | x;",
+                "  | ^",
+                ">> 1",
+                ">> \n",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_repl_is_complete() {
+        let complete = [
+            "",
+            "1 + 2",
+            "let x = 1;",
+            "fn(x) { x }",
+            "f(1)(2)",
+            "let = 1;",
+            "1 + )",
+        ];
+        for source in complete {
+            assert!(is_complete(source), "{source:?} should be complete");
+        }
+        let incomplete = [
+            "let f = fn(x) {",
+            "f(1,",
+            "[1, 2",
+            "{\"a\":",
+            "1 +",
+            "let x =",
+            "if (x) { 1 } else",
+            "let s = \"abc",
+            "/* comment",
+            "let x = 1; let y",
+        ];
+        for source in incomplete {
+            assert!(!is_complete(source), "{source:?} should be incomplete");
+        }
+    }
+
+    #[test]
+    fn test_repl_continuation() {
+        let input = "let add = fn(a, b) {\n  a +\n  b\n};\nadd(1,\n2)\n";
+        assert_eq!(
+            session(input),
+            format!(
+                "{PROMPT}{c}{c}{c}{PROMPT}{c}3\n{PROMPT}\n",
+                c = CONTINUATION_PROMPT
+            )
+        );
+    }
+
+    #[test]
+    fn test_repl_empty_line_ends_continuation() {
+        let output = session("let f = fn(x) {\n\n2\n");
+        assert!(output.starts_with(&format!("{PROMPT}{CONTINUATION_PROMPT}error: expected")));
+        assert!(output.ends_with(&format!("{PROMPT}2\n{PROMPT}\n")));
+
+        // Input left over at the end is still evaluated.
+        let output = session("1 +");
+        assert!(output.starts_with(&format!(
+            "{PROMPT}{CONTINUATION_PROMPT}\nerror: expected expression, got EOF"
+        )));
+    }
+
+    #[test]
+    fn test_repl_command_tokens() {
+        let output = session(":tokens let x = /* c */ 5;\n");
+        assert_eq!(
+            output,
+            format!(
+                "{PROMPT}1:1 LET\n1:5 IDENT(x)\n1:7 ASSIGN\n1:9 COMMENT(/* c */)\n1:17 INT(5)\n1:18 SEMICOLON\n1:19 EOF\n{PROMPT}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_repl_command_ast() {
+        let output = session(":ast let x = 1 + 2 * y;\n");
+        assert_eq!(
+            output,
+            format!(
+                "{PROMPT}Program [\nlet IDENT(x) = (INT(1) + (INT(2) * VAR(y)))\n]\n{PROMPT}\n"
+            )
+        );
+
+        let output = session(":ast let = 1;\n");
+        assert!(output.contains("Program [\nERROR\n]\nerror: expected IDENT, got ASSIGN"));
+    }
+
+    #[test]
+    fn test_repl_command_env_and_reset() {
+        let output = session("let b = \"two\";\nlet a = [1];\n:env\n:reset\n:env\na\n");
+        assert_eq!(
+            output,
+            [
+                format!("{PROMPT}{PROMPT}{PROMPT}a = [1]"),
+                "b = \"two\"".to_string(),
+                format!("{PROMPT}{PROMPT}{PROMPT}error: identifier not found: a"),
+            ]
+            .join("\n")
+                + "\n --> 1:1\n  |\n1 | a\n  | ^\n>> \n"
+        );
+    }
+
+    #[test]
+    fn test_repl_command_load() {
+        let path = std::env::temp_dir().join(format!("repl_load_{}.mk", std::process::id()));
+        std::fs::write(&path, "let square = fn(x) {\n  x * x\n};\n")
+            .expect("failed to write script");
+
+        let output = session(&format!(":load {}\nsquare(4)\n", path.display()));
+        std::fs::remove_file(&path).expect("failed to remove script");
+        assert_eq!(output, format!("{PROMPT}{PROMPT}16\n{PROMPT}\n"));
+
+        let output = session(":load /nonexistent/script.mk\n");
+        assert!(output.starts_with(&format!(
+            "{PROMPT}error: cannot read /nonexistent/script.mk"
+        )));
+    }
+
+    #[test]
+    fn test_repl_command_errors() {
+        let output = session(":help\n:nope\n:tokens\n");
+        assert!(output.contains(":tokens <src>  show the tokens of <src>\n"));
+        assert!(
+            output.contains("error: unknown command :nope, type :help for a list of commands\n")
+        );
+        assert!(
+            output.contains("error: wrong usage of :tokens, type :help for a list of commands\n")
+        );
+    }
+
+    #[test]
+    fn test_repl_feed_line() {
+        let mut repl = Repl::new(false);
+        let mut output = Vec::new();
+        repl.feed_line("let f = fn(x) {", &mut output).unwrap();
+        assert_eq!(repl.prompt(), CONTINUATION_PROMPT);
+        repl.cancel();
+        assert_eq!(repl.prompt(), PROMPT);
+        repl.feed_line("let x = [1,", &mut output).unwrap();
+        repl.feed_line("2]", &mut output).unwrap();
+        repl.feed_line("x[1]", &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "2\n");
+    }
+
+    #[test]
+    fn test_repl_completion() {
+        let mut repl = Repl::new(false);
+        repl.feed_line("let length = 1;", &mut Vec::new()).unwrap();
+        let names = repl.completion_names();
+        assert!(names.contains(&"return".to_string()));
+        assert!(names.contains(&"puts".to_string()));
+        assert!(names.contains(&"length".to_string()));
+
+        assert_eq!(
+            complete("1 + le", 6, &names),
+            (
+                4,
+                vec!["len".to_string(), "length".to_string(), "let".to_string()]
+            )
+        );
+        assert_eq!(
+            complete("f(re", 4, &names),
+            (2, vec!["rest".to_string(), "return".to_string()])
+        );
+        assert_eq!(
+            complete("fi + 1", 2, &names),
+            (0, vec!["first".to_string()])
+        );
+        assert_eq!(complete("1 + ", 4, &names), (4, vec![]));
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use monkey_interpreter::evaluator::RuntimeErrorKind;
+    use monkey_interpreter::object::Object;
+    use monkey_interpreter::parser::ParseErrorKind;
+    use monkey_interpreter::runner::{self, Failure};
+    use std::io::Write;
+    use std::process::{Command, Output, Stdio};
+
+    fn monkey(args: &[&str], stdin: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_monkey_interpreter"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to start interpreter");
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(stdin.as_bytes())
+            .expect("failed to write stdin");
+        child
+            .wait_with_output()
+            .expect("failed to wait for interpreter")
+    }
+
+    #[test]
+    fn test_runner_run() {
+        assert_eq!(
+            runner::run("#!/usr/bin/env monkey\nlet x = 2;\nx * 3"),
+            Ok(Object::Integer(6))
+        );
+
+        let failure = runner::run("let = 1;").expect_err("expected parse failure");
+        assert!(
+            matches!(&failure, Failure::Parse(errors) if errors[0].kind == ParseErrorKind::UnexpectedToken)
+        );
+        assert_eq!(failure.exit_code(), 65);
+
+        let failure = runner::run("1 / 0").expect_err("expected runtime failure");
+        assert!(
+            matches!(&failure, Failure::Runtime(err) if err.kind == RuntimeErrorKind::DivisionByZero)
+        );
+        assert_eq!(failure.exit_code(), 70);
+        assert_eq!(failure.diagnostics()[0].message, "division by zero");
+    }
+
+    #[test]
+    fn test_cli_expression() {
+        let output = monkey(&["-e", "len(\"abc\") + 1"], "");
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "4\n");
+
+        let output = monkey(&["-e", "let x = 1;"], "");
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    }
+
+    #[test]
+    fn test_cli_run_script() {
+        let path = std::env::temp_dir().join(format!("cli_run_{}.mk", std::process::id()));
+        std::fs::write(
+            &path,
+            "#!/usr/bin/env monkey_interpreter run\nputs(\"hi\");\n1 + 1;\n",
+        )
+        .expect("failed to write script");
+        let output = monkey(&["run", path.to_str().unwrap()], "");
+        std::fs::remove_file(&path).expect("failed to remove script");
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "hi\n");
+
+        let output = monkey(&["run", "/nonexistent/script.mk"], "");
+        assert_eq!(output.status.code(), Some(66));
+    }
+
+    #[test]
+    fn test_cli_stdin() {
+        let output = monkey(&[], "let a = [1, 2];\nputs(push(a, 3));\n");
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "[1, 2, 3]\n");
+    }
+
+    #[test]
+    fn test_cli_exit_codes() {
+        let output = monkey(&["-e", "let x = ;"], "");
+        assert_eq!(output.status.code(), Some(65));
+        assert!(String::from_utf8_lossy(&output.stderr).starts_with("error: expected expression"));
+
+        // Nothing runs when any part of the program fails to parse.
+        let output = monkey(&[], "puts(1);\nlet = 2;\n");
+        assert_eq!(output.status.code(), Some(65));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+
+        let output = monkey(&[], "puts(1);\nmissing;\n");
+        assert_eq!(output.status.code(), Some(70));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+        assert!(String::from_utf8_lossy(&output.stderr).contains("identifier not found: missing"));
+
+        let output = monkey(
+            &[
+                "-e",
+                "let f = fn(n) { if (n < 1) { 0 } else { 1 + f(n - 1) } }; f(20000)",
+            ],
+            "",
+        );
+        assert_eq!(output.status.code(), Some(70));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("error: stack overflow"));
+
+        let output = monkey(&["-x"], "");
+        assert_eq!(output.status.code(), Some(64));
+    }
+}